@@ -0,0 +1,468 @@
+//! Build-time checks and staging for the embedded markdown content.
+//!
+//! Posts are embedded in the binary, so a typo in a frontmatter block would
+//! otherwise only show up as a missing post at runtime. Here we parse every
+//! post with the same code the app uses and fail the build on errors.
+//!
+//! Valid public posts are copied into `$OUT_DIR/blog_posts`, which is what
+//! the app embeds with `include_dir!`. Private posts are encrypted (see
+//! `src/vault.rs`) and written to `$OUT_DIR/private_posts.rs` as ciphertext
+//! only. The main password comes from `PRIVATE_BLOG_PASSWORD` and opens every
+//! post; a post with `access: close-friends` can additionally be opened with
+//! `PRIVATE_BLOG_PASSWORD_CLOSE_FRIENDS`. Posts marked
+//! `draft: true` are only kept in debug builds and native builds, so the
+//! released web build never contains them.
+//!
+//! Projects in `projects/` and pages in `pages/` are validated the same way
+//! and embedded directly.
+//! So is `site.toml`; the avatar it names is copied to `$OUT_DIR/avatar`.
+//! `SITE_ROUTING` must name a routing mode (see `src/routing.rs`).
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[allow(dead_code)]
+#[path = "src/frontmatter.rs"]
+mod frontmatter;
+
+#[allow(dead_code)]
+#[path = "src/pages.rs"]
+mod pages;
+
+#[allow(dead_code)]
+#[path = "src/projects.rs"]
+mod projects;
+
+#[allow(dead_code)]
+#[path = "src/routing.rs"]
+mod routing;
+
+#[allow(dead_code)]
+#[path = "src/site.rs"]
+mod site;
+
+#[allow(dead_code)]
+#[path = "src/vault.rs"]
+mod vault;
+
+const BLOG_POSTS_DIR: &str = "blog_posts";
+const PRIVATE_BLOG_POSTS_DIR: &str = "private_blog_posts";
+const PROJECTS_DIR: &str = "projects";
+const PAGES_DIR: &str = "pages";
+const SITE_CONFIG: &str = "site.toml";
+const PASSWORD_VAR: &str = "PRIVATE_BLOG_PASSWORD";
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/frontmatter.rs");
+    println!("cargo:rerun-if-changed=src/pages.rs");
+    println!("cargo:rerun-if-changed=src/projects.rs");
+    println!("cargo:rerun-if-changed=src/routing.rs");
+    println!("cargo:rerun-if-changed=src/site.rs");
+    println!("cargo:rerun-if-changed=src/vault.rs");
+    println!("cargo:rerun-if-env-changed={}", PASSWORD_VAR);
+    println!("cargo:rerun-if-env-changed={}", routing::ROUTING_VAR);
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    let include_drafts = env::var_os("CARGO_CFG_DEBUG_ASSERTIONS").is_some()
+        || env::var("CARGO_CFG_TARGET_ARCH").is_ok_and(|arch| arch != "wasm32");
+
+    let mut errors = Vec::new();
+
+    let (public_posts, public_errors) = collect_posts(
+        Path::new(BLOG_POSTS_DIR),
+        include_drafts,
+        pages::RESERVED_POST_SLUGS,
+    );
+    errors.extend(public_errors);
+    errors.extend(stage_posts(&out_dir.join(BLOG_POSTS_DIR), &public_posts));
+
+    let (private_posts, private_errors) =
+        collect_posts(Path::new(PRIVATE_BLOG_POSTS_DIR), include_drafts, &[]);
+    errors.extend(private_errors);
+
+    errors.extend(check_markdown_dir(
+        Path::new(PROJECTS_DIR),
+        |name, content| {
+            projects::parse(name, content)
+                .map(drop)
+                .map_err(|err| err.to_string())
+        },
+    ));
+    errors.extend(check_markdown_dir(Path::new(PAGES_DIR), check_page));
+    errors.extend(check_site(Path::new(SITE_CONFIG), &out_dir.join("avatar")));
+    errors.extend(check_routing());
+
+    if !errors.is_empty() {
+        panic!(
+            "{} malformed post(s), project(s), page(s) or site setting(s):\n  {}",
+            errors.len(),
+            errors.join("\n  ")
+        );
+    }
+
+    let sealed = seal_posts(&private_posts);
+    fs::write(out_dir.join("private_posts.rs"), sealed.to_rust())
+        .expect("failed to write private_posts.rs");
+}
+
+/// A post that passed validation.
+struct Post {
+    file_name: String,
+    content: String,
+    access: Option<String>,
+}
+
+/// Validate every post in `dir`, returning the publishable ones and any
+/// errors. No post may use one of the `reserved` slugs.
+fn collect_posts(dir: &Path, include_drafts: bool, reserved: &[&str]) -> (Vec<Post>, Vec<String>) {
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (
+            Vec::new(),
+            vec![format!("{}: cannot read directory", dir.display())],
+        );
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    paths.sort();
+
+    let mut posts = Vec::new();
+    let mut errors = Vec::new();
+    // Slug to where it was set, to report the first post when another one
+    // claims the same URL.
+    let mut slugs: BTreeMap<String, String> = BTreeMap::new();
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let name = path.display().to_string();
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) => {
+                errors.push(format!("{}: {}", name, err));
+                continue;
+            }
+        };
+
+        let meta = match frontmatter::parse(&name, &content) {
+            Ok((meta, _)) => meta,
+            Err(err) => {
+                errors.push(err.to_string());
+                continue;
+            }
+        };
+
+        // Drafts are checked too, so publishing one can't break the build.
+        let (slug, location) = post_slug(&name, &path, &content, &meta);
+        if !frontmatter::is_url_safe_slug(&slug) {
+            errors.push(format!(
+                "{}: slug {:?} is not URL-safe (use ASCII letters, digits, `-` and `_`)",
+                location, slug
+            ));
+        } else if reserved.contains(&slug.as_str()) {
+            errors.push(format!(
+                "{}: slug `{}` is already a path of the blog; pick another",
+                location, slug
+            ));
+        } else if let Some(first) = slugs.get(&slug) {
+            errors.push(format!(
+                "{}: slug `{}` is already used by {}",
+                location, slug, first
+            ));
+        } else {
+            slugs.insert(slug, location);
+        }
+
+        if meta.draft && !include_drafts {
+            continue;
+        }
+        let file_name = path
+            .file_name()
+            .expect("read_dir yields file names")
+            .to_string_lossy()
+            .into_owned();
+        posts.push(Post {
+            file_name,
+            content,
+            access: meta.access,
+        });
+    }
+    (posts, errors)
+}
+
+/// The slug a post is served under, as the app derives it, and where it
+/// comes from: the `slug:` line, or else the file name.
+fn post_slug(
+    name: &str,
+    path: &Path,
+    content: &str,
+    meta: &frontmatter::PostMeta,
+) -> (String, String) {
+    match &meta.slug {
+        Some(slug) => {
+            let line = frontmatter::Block::split(name, content)
+                .ok()
+                .and_then(|block| block.key_line("slug"));
+            let location = match line {
+                Some(line) => format!("{}:{}", name, line),
+                None => name.to_string(),
+            };
+            (slug.clone(), location)
+        }
+        None => {
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            (stem, name.to_string())
+        }
+    }
+}
+
+/// Run `check` on every markdown file in `dir`. Projects and pages are
+/// embedded as-is, so there is nothing to stage.
+fn check_markdown_dir(dir: &Path, check: impl Fn(&str, &str) -> Result<(), String>) -> Vec<String> {
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![format!("{}: cannot read directory", dir.display())];
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    paths.sort();
+
+    let mut errors = Vec::new();
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+        let name = path.display().to_string();
+        match fs::read_to_string(&path) {
+            Ok(content) => {
+                if let Err(err) = check(&name, &content) {
+                    errors.push(err);
+                }
+            }
+            Err(err) => errors.push(format!("{}: {}", name, err)),
+        }
+    }
+    errors
+}
+
+/// A page must parse and must not take the path of a built-in section.
+fn check_page(name: &str, content: &str) -> Result<(), String> {
+    pages::parse(name, content).map_err(|err| err.to_string())?;
+    let slug = Path::new(name)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if pages::RESERVED_SLUGS.contains(&slug.as_str()) {
+        return Err(format!(
+            "{}: /{} is already a section of the site; rename the file",
+            name, slug
+        ));
+    }
+    Ok(())
+}
+
+fn check_routing() -> Option<String> {
+    let name = env::var(routing::ROUTING_VAR).unwrap_or_default();
+    if routing::RoutingMode::from_name(&name).is_some() {
+        return None;
+    }
+    let modes: Vec<&str> = routing::RoutingMode::ALL
+        .iter()
+        .map(|mode| mode.name())
+        .collect();
+    Some(format!(
+        "{}: unknown routing mode `{}`, expected one of {}",
+        routing::ROUTING_VAR,
+        name,
+        modes.join(", ")
+    ))
+}
+
+/// Validate `site.toml` and copy the avatar it names to `avatar`, or write
+/// an empty file there if it names none, so the app can always embed it.
+fn check_site(path: &Path, avatar: &Path) -> Vec<String> {
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let config = match fs::read_to_string(path) {
+        Ok(content) => match site::parse(&content) {
+            Ok(config) => config,
+            Err(err) => return vec![err.to_string()],
+        },
+        Err(err) => return vec![format!("{}: {}", path.display(), err)],
+    };
+
+    let bytes = match &config.profile.avatar {
+        Some(file) => {
+            println!("cargo:rerun-if-changed={}", file);
+            match fs::read(file) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    return vec![format!(
+                        "{}: profile.avatar: {}: {}",
+                        path.display(),
+                        file,
+                        err
+                    )]
+                }
+            }
+        }
+        None => Vec::new(),
+    };
+    fs::write(avatar, bytes)
+        .err()
+        .map(|err| format!("{}: {}", avatar.display(), err))
+        .into_iter()
+        .collect()
+}
+
+/// Copy posts into `staged`, replacing whatever a previous build left there.
+fn stage_posts(staged: &Path, posts: &[Post]) -> Vec<String> {
+    // Start from scratch so deleted posts and new drafts don't linger.
+    let _ = fs::remove_dir_all(staged);
+    if let Err(err) = fs::create_dir_all(staged) {
+        return vec![format!("{}: {}", staged.display(), err)];
+    }
+
+    posts
+        .iter()
+        .filter_map(|post| {
+            let target = staged.join(&post.file_name);
+            fs::write(&target, &post.content)
+                .err()
+                .map(|err| format!("{}: {}", target.display(), err))
+        })
+        .collect()
+}
+
+type Nonce = [u8; vault::NONCE_LEN];
+
+struct SealedGroup {
+    name: String,
+    keys: Vec<(Nonce, Vec<u8>)>,
+    posts: Vec<(Nonce, Vec<u8>)>,
+}
+
+struct SealedPosts {
+    salt: [u8; vault::SALT_LEN],
+    groups: Vec<SealedGroup>,
+}
+
+impl SealedPosts {
+    /// Render as Rust source for `include!` in the app.
+    fn to_rust(&self) -> String {
+        let mut source = String::from("// @generated by build.rs from private_blog_posts/\n");
+        let _ = writeln!(
+            source,
+            "pub const SALT: [u8; {}] = {:?};",
+            vault::SALT_LEN,
+            self.salt
+        );
+        let _ = writeln!(source, "pub const ITERATIONS: u32 = {};", vault::ITERATIONS);
+        source.push_str("pub static GROUPS: &[crate::vault::SealedGroup] = &[\n");
+        for group in &self.groups {
+            let _ = writeln!(source, "    crate::vault::SealedGroup {{");
+            let _ = writeln!(source, "        name: {:?},", group.name);
+            for (field, sealed) in [("keys", &group.keys), ("posts", &group.posts)] {
+                let _ = writeln!(source, "        {}: &[", field);
+                for (nonce, ciphertext) in sealed {
+                    let _ = writeln!(
+                        source,
+                        "            crate::vault::Sealed {{ nonce: {:?}, ciphertext: &{:?} }},",
+                        nonce, ciphertext
+                    );
+                }
+                let _ = writeln!(source, "        ],");
+            }
+            let _ = writeln!(source, "    }},");
+        }
+        source.push_str("];\n");
+        source
+    }
+}
+
+fn random<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    getrandom::getrandom(&mut bytes).expect("no system randomness available");
+    bytes
+}
+
+/// The password for an access group, from `PRIVATE_BLOG_PASSWORD_<GROUP>`.
+fn group_password(group: &str) -> Option<String> {
+    let var = format!(
+        "{}_{}",
+        PASSWORD_VAR,
+        group.to_ascii_uppercase().replace('-', "_")
+    );
+    println!("cargo:rerun-if-env-changed={}", var);
+    env::var(var).ok().filter(|password| !password.is_empty())
+}
+
+fn seal_posts(posts: &[Post]) -> SealedPosts {
+    let salt = random::<{ vault::SALT_LEN }>();
+    let derive = |password: &str| vault::derive_key(password, &salt, vault::ITERATIONS);
+    let main_key = env::var(PASSWORD_VAR)
+        .ok()
+        .filter(|password| !password.is_empty())
+        .map(|password| derive(&password));
+
+    let mut by_group: BTreeMap<&str, Vec<&Post>> = BTreeMap::new();
+    for post in posts {
+        let group = post.access.as_deref().unwrap_or(vault::DEFAULT_GROUP);
+        by_group.entry(group).or_default().push(post);
+    }
+
+    let mut groups = Vec::new();
+    for (name, posts) in by_group {
+        let mut password_keys: Vec<vault::SecretKey> = main_key.into_iter().collect();
+        if name != vault::DEFAULT_GROUP {
+            match group_password(name) {
+                Some(password) => password_keys.push(derive(&password)),
+                None => println!(
+                    "cargo:warning=no password for access group `{}`; only {} will open it",
+                    name, PASSWORD_VAR
+                ),
+            }
+        }
+        if password_keys.is_empty() {
+            println!(
+                "cargo:warning={} is not set; building without {} private post(s) in `{}`",
+                PASSWORD_VAR,
+                posts.len(),
+                name
+            );
+            continue;
+        }
+
+        let content_key = random::<{ vault::KEY_LEN }>();
+        let seal = |key: &vault::SecretKey, plaintext: &[u8]| {
+            let nonce = random::<{ vault::NONCE_LEN }>();
+            (nonce, vault::encrypt(key, &nonce, plaintext))
+        };
+
+        groups.push(SealedGroup {
+            name: name.to_string(),
+            keys: password_keys
+                .iter()
+                .map(|key| seal(key, &content_key))
+                .collect(),
+            posts: posts
+                .iter()
+                .map(|post| {
+                    let plaintext = format!("{}\n{}", post.file_name, post.content);
+                    seal(&content_key, plaintext.as_bytes())
+                })
+                .collect(),
+        });
+    }
+
+    SealedPosts { salt, groups }
+}
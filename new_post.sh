@@ -9,7 +9,8 @@ read -rp "Post title: " TITLE
 
 # 2) Generate ISO-8601 timestamp with colon in timezone offset
 #    GNU date supports %:z, BSD does not.
-if TS=$(date +"%Y-%m-%dT%H:%M:%S%:z" 2>/dev/null); then
+#    BSD date prints an unknown %:z literally, so check the output as well.
+if TS=$(date +"%Y-%m-%dT%H:%M:%S%:z" 2>/dev/null) && [[ "$TS" != *":z" ]]; then
   : # Successful GNU format
 else
   BASE=$(date +"%Y-%m-%dT%H:%M:%S")
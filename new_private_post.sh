@@ -9,7 +9,8 @@ read -rp "Private post title: " TITLE
 
 # 2) Make an ISO-8601 local timestamp with colon in the timezone offset
 #    GNU date supports %:z; BSD/macOS doesn't, so we fall back and insert the colon.
-if TS=$(date +"%Y-%m-%dT%H:%M:%S%:z" 2>/dev/null); then
+#    BSD date prints an unknown %:z literally, so check the output as well.
+if TS=$(date +"%Y-%m-%dT%H:%M:%S%:z" 2>/dev/null) && [[ "$TS" != *":z" ]]; then
   : # GNU date path
 else
   BASE=$(date +"%Y-%m-%dT%H:%M:%S")
@@ -1,13 +1,28 @@
-use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, Utc};
 use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
 use egui_extras::{Size, StripBuilder};
 use include_dir::{include_dir, Dir};
 
-static BLOG_POSTS_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/blog_posts");
-static PRIVATE_BLOG_POSTS_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/private_blog_posts");
+use crate::frontmatter::{self, FrontmatterError, PostMeta};
+use crate::headings::{Heading, Outline};
+use crate::nav::{NavItem, NavRegistry, NavTarget};
+use crate::pages::{self, PageMeta};
+use crate::projects::{self, ProjectMeta};
+#[cfg(target_arch = "wasm32")]
+use crate::routing::RoutingMode;
+use crate::site;
+use crate::stats::PostStats;
+
+mod sections;
+
+pub use sections::ProjectFilter;
+use sections::{BlogState, PrivateBlogState, ProjectsState};
 
-// Password for private blog posts (in production, use environment variables or secure storage)
-const PRIVATE_BLOG_PASSWORD: &str = "austin2321";
+// Validated by build.rs, embedded as-is.
+static PROJECTS_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/projects");
+
+// Validated by build.rs, embedded as-is.
+static PAGES_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/pages");
 
 #[derive(Clone)]
 pub struct BlogPost {
@@ -16,54 +31,340 @@ pub struct BlogPost {
     pub content: String,
     pub slug: String,
     pub published_at: DateTime<Utc>,
+    /// Counted from `content` when the post is loaded.
+    pub stats: PostStats,
+    /// The headings of `content` and the pieces it is drawn in.
+    pub outline: Outline,
+    /// Everything else declared in the post's frontmatter.
+    pub meta: PostMeta,
+}
+
+impl BlogPost {
+    /// Parse markdown files given as `(file name, contents)`, newest first.
+    pub fn load_all<'a>(files: impl Iterator<Item = (&'a str, &'a str)>) -> Vec<BlogPost> {
+        let mut posts = Vec::new();
+
+        // Automatically discover all .md files
+        for (name, content) in files {
+            let path = std::path::Path::new(name);
+            if path.extension().is_some_and(|extension| extension == "md") {
+                let slug = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                // `build.rs` rejects malformed posts, so this only fires if
+                // the check was bypassed.
+                match Self::parse(name, content, slug) {
+                    Ok(post) => posts.push(post),
+                    Err(err) => log::error!("Skipping post: {}", err),
+                }
+            }
+        }
+
+        // Sort posts by published timestamp (newest first)
+        posts.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+
+        posts
+    }
+
+    /// Read and parse every post in a directory, for the native build tools
+    /// in `src/bin/`. The app itself uses the copies embedded by `build.rs`.
+    pub fn read_dir(dir: &std::path::Path) -> std::io::Result<Vec<BlogPost>> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if let Some(name) = path.file_name().and_then(|name| name.to_str()) {
+                files.push((name.to_string(), std::fs::read_to_string(&path)?));
+            }
+        }
+        Ok(Self::load_all(
+            files
+                .iter()
+                .map(|(name, content)| (name.as_str(), content.as_str())),
+        ))
+    }
+
+    /// Parse one post; `slug` is used unless the frontmatter overrides it.
+    pub fn parse(file: &str, content: &str, slug: String) -> Result<BlogPost, FrontmatterError> {
+        let (meta, body) = frontmatter::parse(file, content)?;
+
+        Ok(BlogPost {
+            title: meta.title.clone(),
+            date_display: meta.date.format("%B %-d, %Y").to_string(),
+            stats: PostStats::of(&body),
+            outline: Outline::of(&body),
+            content: body,
+            slug: meta.slug.clone().unwrap_or(slug),
+            published_at: meta.published_at(),
+            meta,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct Project {
+    /// From the file name.
+    pub slug: String,
+    pub meta: ProjectMeta,
+    /// Markdown write-up after the frontmatter, possibly empty.
+    pub content: String,
+}
+
+impl Project {
+    /// Every project in `projects/`, in display order.
+    pub fn embedded() -> Vec<Project> {
+        let mut projects = Vec::new();
+        for (name, content) in MyApp::embedded_files(&PROJECTS_DIR) {
+            let path = std::path::Path::new(name);
+            if !path.extension().is_some_and(|extension| extension == "md") {
+                continue;
+            }
+            let slug = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            // `build.rs` rejects malformed projects, so this only fires if
+            // the check was bypassed.
+            match projects::parse(name, content) {
+                Ok((meta, content)) => projects.push(Project {
+                    slug,
+                    meta,
+                    content,
+                }),
+                Err(err) => log::error!("Skipping project: {}", err),
+            }
+        }
+        projects.sort_by(|a, b| a.meta.display_order(&b.meta));
+        projects
+    }
+}
+
+/// A standalone page from `pages/`, served at `/{slug}`.
+#[derive(Clone)]
+pub struct MarkdownPage {
+    /// From the file name.
+    pub slug: String,
+    pub meta: PageMeta,
+    pub content: String,
+    /// The headings of `content` and the pieces it is drawn in.
+    pub outline: Outline,
+}
+
+impl MarkdownPage {
+    /// Every page in `pages/`, parsed on first use. The router needs them
+    /// before there is an app to keep them in.
+    pub fn embedded() -> &'static [MarkdownPage] {
+        static PAGES: std::sync::OnceLock<Vec<MarkdownPage>> = std::sync::OnceLock::new();
+        PAGES.get_or_init(|| {
+            let mut markdown_pages = Vec::new();
+            for (name, content) in MyApp::embedded_files(&PAGES_DIR) {
+                let path = std::path::Path::new(name);
+                if !path.extension().is_some_and(|extension| extension == "md") {
+                    continue;
+                }
+                let slug = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                // `build.rs` rejects malformed pages, so this only fires if
+                // the check was bypassed.
+                match pages::parse(name, content) {
+                    Ok((meta, content)) => markdown_pages.push(MarkdownPage {
+                        slug,
+                        meta,
+                        outline: Outline::of(&content),
+                        content,
+                    }),
+                    Err(err) => log::error!("Skipping page: {}", err),
+                }
+            }
+            markdown_pages.sort_by(|a, b| a.slug.cmp(&b.slug));
+            markdown_pages
+        })
+    }
+
+    pub fn find(slug: &str) -> Option<&'static MarkdownPage> {
+        Self::embedded().iter().find(|page| page.slug == slug)
+    }
 }
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Eq, Debug)]
-enum Page {
+pub enum Page {
     Home,
+    /// Filtered by the route's query; see [`ProjectFilter`].
     Projects,
+    Project(String),
     Blog,
     BlogPost(String),
+    BlogTags,
+    BlogTag(String),
+    BlogSearch(String),
+    BlogStats,
     PrivateBlog,
     PrivateBlogPost(String),
+    /// A page of a section without a variant of its own (see
+    /// `src/app/sections.rs`): its path without the leading `/`.
+    Section(String),
+    /// Nothing lives at this path (kept as requested, with its query), so
+    /// the address bar keeps showing the broken link.
+    NotFound(String),
 }
 
 impl Page {
     #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
     fn from_path(path: &str) -> Self {
-        let trimmed = path.trim();
-        let trimmed = trimmed.trim_start_matches('/');
+        sections::route(path)
+    }
 
-        if trimmed.is_empty() {
-            return Page::Home;
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    pub fn to_url(&self) -> String {
+        match sections::section_for(self) {
+            Some(section) => section.to_url(self),
+            // A page no section has any more, e.g. one deleted since the
+            // app state was saved.
+            None => match self {
+                Page::Section(path) => format!("/{}", path),
+                _ => "/".to_string(),
+            },
         }
+    }
 
-        let mut segments = trimmed.split('/');
-        let first = segments.next();
-        let second = segments.next();
-
-        match (first, second) {
-            (Some("projects"), _) => Page::Projects,
-            (Some("blog"), None | Some("")) => Page::Blog,
-            (Some("blog"), Some(slug)) => Page::BlogPost(slug.to_string()),
-            (Some("private"), None | Some("")) => Page::PrivateBlog,
-            (Some("private"), Some(slug)) => Page::PrivateBlogPost(slug.to_string()),
-            _ => Page::Home,
-        }
+    /// Whether this page is `section` or one of its sub-pages (for nav highlighting).
+    pub(crate) fn is_within(&self, section: &Page) -> bool {
+        sections::section_for(section)
+            .is_some_and(|owner| owner.owns(self) && owner.is_within(self, section))
+    }
+
+    /// Whether the page keeps query parameter `key` itself and writes it
+    /// back in [`Self::to_url`].
+    fn keeps_query_param(&self, key: &str) -> bool {
+        sections::section_for(self).is_some_and(|section| section.keeps_query_param(self, key))
     }
+}
+
+/// A page together with the parts of its URL the page doesn't keep itself:
+/// other query parameters (`?page=2`) and the fragment (`#why-rust`).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Route {
+    pub page: Page,
+    /// Decoded `key=value` pairs, in URL order.
+    pub query: Vec<(String, String)>,
+    /// Without the `#`; usually a heading anchor.
+    pub fragment: Option<String>,
+}
 
+impl Route {
+    /// Parse a URL such as `/blog/my-post#why-rust`, without scheme or host.
     #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
-    fn to_url(&self) -> String {
-        match self {
-            Page::Home => "/".to_string(),
-            Page::Projects => "/projects".to_string(),
-            Page::Blog => "/blog".to_string(),
-            Page::BlogPost(slug) => format!("/blog/{}", slug),
-            Page::PrivateBlog => "/private".to_string(),
-            Page::PrivateBlogPost(slug) => format!("/private/{}", slug),
+    pub fn from_url(url: &str) -> Self {
+        let (path, fragment) = url.split_once('#').unwrap_or((url, ""));
+        let page = Page::from_path(path);
+        let query = path.split_once('?').map_or("", |(_, query)| query);
+        let query = query_pairs(query)
+            .filter(|(key, _)| !page.keeps_query_param(key))
+            .map(|(key, value)| (key.to_string(), value))
+            .collect();
+        Self {
+            page,
+            query,
+            fragment: Some(fragment.to_string()).filter(|fragment| !fragment.is_empty()),
+        }
+    }
+
+    /// The inverse of [`Self::from_url`].
+    pub fn to_url(&self) -> String {
+        let mut url = self.page.to_url();
+        for (index, (key, value)) in self.query.iter().enumerate() {
+            let separator = if index == 0 && !url.contains('?') {
+                '?'
+            } else {
+                '&'
+            };
+            url.push(separator);
+            url.push_str(&encode_query_value(key));
+            url.push('=');
+            url.push_str(&encode_query_value(value));
+        }
+        if let Some(fragment) = &self.fragment {
+            url.push('#');
+            url.push_str(fragment);
+        }
+        url
+    }
+}
+
+impl From<Page> for Route {
+    fn from(page: Page) -> Self {
+        Self {
+            page,
+            query: Vec::new(),
+            fragment: None,
+        }
+    }
+}
+
+/// The `key=value` pairs of an `a=b&c=d` query string, values percent-decoded.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn query_pairs(query: &str) -> impl Iterator<Item = (&str, String)> {
+    query
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key, decode_query_value(value))
+        })
+}
+
+/// Look up `key` in an `a=b&c=d` query string and percent-decode its value.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn query_param(query: &str, key: &str) -> Option<String> {
+    query_pairs(query)
+        .find(|(name, _)| *name == key)
+        .map(|(_, value)| value)
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn encode_query_value(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
         }
     }
+    encoded
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn decode_query_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex = value
+                    .get(index + 1..index + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match hex {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        index += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
 }
 
 struct ThemeToggleButton {
@@ -276,6 +577,67 @@ impl egui::Widget for NavMenuButton<'_> {
     }
 }
 
+struct TagChip {
+    text: String,
+    selected: bool,
+}
+
+impl TagChip {
+    /// A blog tag, shown as `#tag`.
+    fn new(tag: &str, selected: bool) -> Self {
+        Self::plain(&format!("#{}", tag), selected)
+    }
+
+    /// A chip showing `text` as-is, for filters that aren't tags.
+    fn plain(text: &str, selected: bool) -> Self {
+        Self {
+            text: text.to_string(),
+            selected,
+        }
+    }
+}
+
+impl egui::Widget for TagChip {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let padding = egui::vec2(8.0, 2.0);
+        let text_style = egui::TextStyle::Small;
+
+        let text =
+            egui::WidgetText::from(egui::RichText::new(self.text).text_style(text_style.clone()));
+        let galley = text.into_galley(ui, Some(false), f32::INFINITY, text_style);
+
+        let size = galley.size() + padding * 2.0;
+        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+        let visuals = ui.style().interact_selectable(&response, self.selected);
+
+        if ui.is_rect_visible(rect) {
+            let accent = ui.visuals().hyperlink_color;
+            let fill = if self.selected || response.hovered() {
+                accent.linear_multiply(0.25)
+            } else {
+                accent.linear_multiply(0.1)
+            };
+
+            let painter = ui.painter_at(rect);
+            painter.rect(
+                rect,
+                egui::Rounding::same(rect.height() * 0.5),
+                fill,
+                egui::Stroke::NONE,
+            );
+            painter.galley(rect.min + padding, galley, visuals.text_color());
+        }
+
+        response.on_hover_cursor(egui::CursorIcon::PointingHand)
+    }
+}
+
+/// Posts and pages with fewer headings get no table of contents.
+const TOC_MIN_HEADINGS: usize = 3;
+/// From this window width the table of contents is a side panel; below it,
+/// a dropdown above the text.
+const TOC_PANEL_MIN_WIDTH: f32 = 1000.0;
+
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
 pub struct MyApp {
@@ -287,22 +649,34 @@ pub struct MyApp {
     current_page: Page,
     prefer_dark: bool,
 
-    #[serde(skip)] // Don't serialize blog posts and cache
-    blog_posts: Vec<BlogPost>,
-    selected_blog: Option<usize>,
-    
-    #[serde(skip)] // Private blog posts
-    private_blog_posts: Vec<BlogPost>,
-    selected_private_blog: Option<usize>,
-    
+    #[serde(skip)] // Posts, search and the selected post; rebuilt on startup
+    blog: BlogState,
+    #[serde(skip)] // Loaded from the embedded `projects/` on startup
+    projects: ProjectsState,
     #[serde(skip)] // Password authentication state (don't serialize for security)
-    is_private_authenticated: bool,
-    password_input: String,
+    private: PrivateBlogState,
+
+    #[serde(skip)] // Built from `site.toml` and the sections on startup
+    nav: NavRegistry,
+    #[serde(skip)] // Last title given to the window or tab
+    window_title: String,
 
     #[serde(skip)] // Don't serialize the cache
     markdown_cache: CommonMarkCache,
     #[serde(skip)] // UI-only overlay state
     show_mobile_menu: bool,
+    #[serde(skip)] // Last read from or written to the address bar
+    route: Route,
+    #[serde(skip)] // Scroll to the heading in the route's fragment once it is drawn
+    scroll_to_fragment: bool,
+    #[serde(skip)] // The page `scroll_offset` belongs to
+    scrolled_page: Page,
+    #[serde(skip)] // Vertical scroll of the page after the last frame
+    scroll_offset: f32,
+    #[serde(skip)] // Scroll stored in the current browser history entry
+    recorded_scroll: f32,
+    #[serde(skip)] // Scroll to apply on the next frame, from history
+    restore_scroll: Option<f32>,
 }
 
 impl Default for MyApp {
@@ -313,14 +687,19 @@ impl Default for MyApp {
             value: 2.7,
             current_page: Page::Home,
             prefer_dark: true,
-            blog_posts: Self::create_sample_blog_posts(),
-            selected_blog: None,
-            private_blog_posts: Self::create_private_blog_posts(),
-            selected_private_blog: None,
-            is_private_authenticated: false,
-            password_input: String::new(),
+            blog: BlogState::default(),
+            projects: ProjectsState::default(),
+            private: PrivateBlogState::default(),
+            nav: Self::nav_registry(),
+            window_title: String::new(),
             markdown_cache: CommonMarkCache::default(),
             show_mobile_menu: false,
+            route: Route::from(Page::Home),
+            scroll_to_fragment: false,
+            scrolled_page: Page::Home,
+            scroll_offset: 0.0,
+            recorded_scroll: 0.0,
+            restore_scroll: None,
         }
     }
 }
@@ -341,6 +720,20 @@ impl MyApp {
         }
     }
 
+    /// The accent color from `site.toml`.
+    fn accent() -> egui::Color32 {
+        let [r, g, b] = site::embedded().accent;
+        egui::Color32::from_rgb(r, g, b)
+    }
+
+    /// Use the accent color for links and text selection.
+    fn apply_accent(visuals: &mut egui::Visuals) {
+        let accent = Self::accent();
+        visuals.hyperlink_color = accent;
+        visuals.selection.bg_fill = accent.linear_multiply(0.2);
+        visuals.selection.stroke.color = accent;
+    }
+
     fn ensure_theme(&self, ctx: &egui::Context) {
         let desired_dark = self.prefer_dark;
         if ctx.style().visuals.dark_mode != desired_dark {
@@ -349,10 +742,7 @@ impl MyApp {
             } else {
                 egui::Visuals::light()
             };
-            let accent = egui::Color32::from_rgb(22, 163, 74);
-            visuals.hyperlink_color = accent;
-            visuals.selection.bg_fill = accent.linear_multiply(0.2);
-            visuals.selection.stroke.color = accent;
+            Self::apply_accent(&mut visuals);
             ctx.set_visuals(visuals);
         }
     }
@@ -407,6 +797,7 @@ impl MyApp {
         style.spacing.button_padding = egui::vec2(12.0, 8.0);
 
         cc.egui_ctx.set_style(style);
+        egui_extras::install_image_loaders(&cc.egui_ctx);
 
         let mut restored_from_storage = false;
         let mut app: Self = if let Some(storage) = cc.storage {
@@ -421,17 +812,19 @@ impl MyApp {
         }
 
         // Rehydrate transient state that we intentionally skip during serialization.
-        app.blog_posts = Self::create_sample_blog_posts();
-        if let Some(selected) = app.selected_blog {
-            if selected >= app.blog_posts.len() {
-                app.selected_blog = None;
-            }
-        }
         app.markdown_cache = CommonMarkCache::default();
         app.show_mobile_menu = false;
+        app.restore_private_blog(cc.storage, Utc::now());
 
         app.pull_route_from_browser();
-        app.sync_blog_selection_from_route();
+        app.sync_section_from_route();
+        #[cfg(target_arch = "wasm32")]
+        {
+            // After a reload, pick up where the visitor was.
+            app.restore_scroll = history_state().map(|state| state.scroll);
+            app.scroll_to_fragment &= app.restore_scroll.is_none();
+            listen_for_history_navigation(&cc.egui_ctx);
+        }
         app
     }
 }
@@ -440,26 +833,27 @@ impl eframe::App for MyApp {
     /// Called by the frame work to save state before shutdown.
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
         eframe::set_value(storage, eframe::APP_KEY, self);
+        self.save_private_blog(storage);
     }
 
     /// Called each time the UI needs repainting, which may be many times per second.
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         self.ensure_theme(ctx);
 
         let screen_width = ctx.input(|input| input.screen_rect.width());
         let is_compact = screen_width < 520.0;
-        // Only update style if hyperlink color needs to be green (to avoid constant style updates)
-        if ctx.style().visuals.hyperlink_color != egui::Color32::from_rgb(22, 163, 74) {
+        // Only update style if hyperlink color needs to be the accent (to avoid constant style updates)
+        if ctx.style().visuals.hyperlink_color != Self::accent() {
             let mut style = (*ctx.style()).clone();
-            style.visuals.hyperlink_color = egui::Color32::from_rgb(22, 163, 74); // Green color
-            style.visuals.selection.bg_fill =
-                egui::Color32::from_rgb(22, 163, 74).linear_multiply(0.2);
-            style.visuals.selection.stroke.color = egui::Color32::from_rgb(22, 163, 74);
+            Self::apply_accent(&mut style.visuals);
             ctx.set_style(style);
         }
 
-        self.pull_route_from_browser();
-        self.sync_blog_selection_from_route();
+        self.publish_scheduled_posts(ctx);
+        self.expire_private_session(ctx);
+        self.follow_history_navigation();
+        self.sync_section_from_route();
+        self.update_window_title(ctx);
 
         if !is_compact {
             self.show_mobile_menu = false;
@@ -521,20 +915,51 @@ impl eframe::App for MyApp {
             });
         });
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            egui::ScrollArea::vertical()
-                .auto_shrink([false; 2])
-                .show(ui, |ui| match self.current_page.clone() {
-                    Page::Home => self.show_home(ui),
-                    Page::Projects => self.show_projects(ui),
-                    Page::Blog | Page::BlogPost(_) => self.show_blog(ui),
-                    Page::PrivateBlog | Page::PrivateBlogPost(_) => self.show_private_blog(ui),
+        if let Some(footer) = &site::embedded().footer {
+            egui::TopBottomPanel::bottom("footer").show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                    ui.colored_label(egui::Color32::from_rgb(120, 120, 120), footer);
                 });
+            });
+        }
+
+        if screen_width >= TOC_PANEL_MIN_WIDTH {
+            self.show_table_of_contents_panel(ctx);
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let mut scroll_area = egui::ScrollArea::vertical().auto_shrink([false; 2]);
+            // A new page starts at the top, unless history says otherwise.
+            let restore_scroll = self.restore_scroll.take();
+            if self.scrolled_page != self.current_page || restore_scroll.is_some() {
+                self.scrolled_page = self.current_page.clone();
+                scroll_area = scroll_area.vertical_scroll_offset(restore_scroll.unwrap_or(0.0));
+            }
+            let output = scroll_area.show(ui, |ui| {
+                let page = self.current_page.clone();
+                match sections::section_for(&page) {
+                    Some(section) => section.show(self, ui, &page),
+                    // A section that no longer exists, e.g. in restored state.
+                    None => self.show_home(ui),
+                }
+            });
+            self.track_scroll(ctx, output.state.offset.y);
         });
 
         if is_compact && self.show_mobile_menu {
             self.show_mobile_menu_overlay(ctx);
         }
+
+        // Don't wait for the next auto-save: a locked session must not
+        // survive the tab being closed, nor a failed attempt be undone by it.
+        if self.private.storage_dirty {
+            if let Some(storage) = frame.storage_mut() {
+                self.save_private_blog(storage);
+                storage.flush();
+            }
+            self.private.storage_dirty = false;
+        }
     }
 }
 
@@ -542,7 +967,7 @@ impl MyApp {
     fn navigate_to(&mut self, page: Page) -> bool {
         if self.current_page != page {
             self.current_page = page;
-            self.selected_blog = None;
+            self.blog.selected = None;
             true
         } else {
             false
@@ -560,24 +985,9 @@ impl MyApp {
     fn render_nav_links(&mut self, ui: &mut egui::Ui) {
         ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading2".into()));
 
-        let _ = self.nav_item(
-            ui,
-            "Home",
-            matches!(self.current_page, Page::Home),
-            Page::Home,
-        );
-        let _ = self.nav_item(
-            ui,
-            "Projects",
-            matches!(self.current_page, Page::Projects),
-            Page::Projects,
-        );
-        let _ = self.nav_item(
-            ui,
-            "Blog",
-            matches!(self.current_page, Page::Blog | Page::BlogPost(_)),
-            Page::Blog,
-        );
+        for item in self.visible_nav_items() {
+            let _ = self.nav_item(ui, &item);
+        }
     }
 
     fn show_mobile_menu_overlay(&mut self, ctx: &egui::Context) {
@@ -625,693 +1035,475 @@ impl MyApp {
                     ui.vertical(|ui| {
                         ui.set_width(ui.available_width());
 
-                        let home = self.nav_item_full_width(
-                            ui,
-                            "Home",
-                            matches!(self.current_page, Page::Home),
-                            Page::Home,
-                        );
-                        if home.clicked() {
-                            self.show_mobile_menu = false;
-                        }
-
-                        let projects = self.nav_item_full_width(
-                            ui,
-                            "Projects",
-                            matches!(self.current_page, Page::Projects),
-                            Page::Projects,
-                        );
-                        if projects.clicked() {
-                            self.show_mobile_menu = false;
-                        }
-
-                        let blog = self.nav_item_full_width(
-                            ui,
-                            "Blog",
-                            matches!(self.current_page, Page::Blog | Page::BlogPost(_)),
-                            Page::Blog,
-                        );
-                        if blog.clicked() {
-                            self.show_mobile_menu = false;
+                        for item in self.visible_nav_items() {
+                            if self.nav_item_full_width(ui, &item).clicked() {
+                                self.show_mobile_menu = false;
+                            }
                         }
                     });
                 });
             });
     }
 
-    fn nav_item(
-        &mut self,
-        ui: &mut egui::Ui,
-        label: &str,
-        is_selected: bool,
-        target: Page,
-    ) -> egui::Response {
-        let response = ui.add(NavBarButton::new(label, is_selected));
-        if response.clicked() && self.navigate_to(target) {
-            self.push_route_to_browser();
+    /// The `site.toml` nav plus the entries sections add themselves.
+    pub fn nav_registry() -> NavRegistry {
+        let mut nav = NavRegistry::embedded();
+        for item in sections::SECTIONS
+            .iter()
+            .flat_map(|section| section.nav_items())
+        {
+            if !nav.links_to(&item.target) {
+                nav.register(item);
+            }
         }
-        response
+        nav
     }
 
-    fn nav_item_full_width(
-        &mut self,
-        ui: &mut egui::Ui,
-        label: &str,
-        is_selected: bool,
-        target: Page,
-    ) -> egui::Response {
-        let response = ui.add(NavMenuButton::new(label, is_selected));
-        if response.clicked() && self.navigate_to(target) {
-            self.push_route_to_browser();
+    /// Name the window (or browser tab) after the current page.
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    fn update_window_title(&mut self, ctx: &egui::Context) {
+        let title = sections::title(self, &self.current_page);
+        if title == self.window_title {
+            return;
         }
-        response
+        #[cfg(target_arch = "wasm32")]
+        if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            document.set_title(&title);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+        self.window_title = title;
     }
 
-    fn show_home(&mut self, ui: &mut egui::Ui) {
-        ui.add_space(24.0); // Top margin
-
-        // Calculate responsive margins
-        let screen_width = ui.available_width();
-        let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
-
-        ui.horizontal(|ui| {
-            ui.add_space(left_margin); // Responsive left margin
-
-            ui.vertical(|ui| {
-                // Calculate the content width by subtracting both margins
-                let content_width = ui.available_width() - right_margin;
-                ui.set_max_width(content_width);
-
-                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
-                ui.label("Austin Liu");
-
-                ui.add_space(16.0); // Design system spacing
-
-                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading2".into()));
-                ui.hyperlink_to("Github", "https://github.com/ostenloo");
-                ui.hyperlink_to("Linkedin", "https://www.linkedin.com/in/austindasunliu/");
-                ui.hyperlink_to("Resume", "https://drive.google.com/file/d/1L3IDJ-S8pwfDuSa4uCvNRD7HRwkQ9olX/view?usp=sharing");
-            });
-        });
+    /// The nav entries to draw right now, in order.
+    fn visible_nav_items(&self) -> Vec<NavItem> {
+        self.nav
+            .visible(self.private.is_authenticated)
+            .cloned()
+            .collect()
     }
 
-    fn show_projects(&mut self, ui: &mut egui::Ui) {
-        ui.add_space(24.0); // Top margin
-
-        // Calculate responsive margins
-        let screen_width = ui.available_width();
-        let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
-
-        ui.horizontal(|ui| {
-            ui.add_space(left_margin); // Responsive left margin
-
-            ui.vertical(|ui| {
-                // Calculate the content width by subtracting both margins
-                let content_width = ui.available_width() - right_margin;
-                ui.set_max_width(content_width);
-
-                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
-                ui.label("Projects");
-
-                ui.add_space(32.0); // Design system H1 bottom margin
-
-                // Project entries with design system spacing
-                ui.spacing_mut().item_spacing.y = 32.0; // More space between projects
-
-                // Zeitgus
-                ui.vertical(|ui| {
-                    ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading2".into()));
-                    ui.hyperlink_to("Zeitgus", "https://www.zeitgus.com");
-                    ui.add_space(8.0);
-                    ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
-                    ui.label("I tried to build a startup in college, all I have is a landing page to show for it.");
-                });
-
-                // FIDE ratings database
-                ui.vertical(|ui| {
-                    ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading2".into()));
-                    ui.hyperlink_to("FIDE Ratings Database", "https://console.cloud.google.com/bigquery?ws=!1m5!1m4!4m3!1scalm-premise-334401!2sFIDE_ratings!3sFIDE_ratings_2");
-                    ui.add_space(8.0);
-                    ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
-                    ui.label("Chess ratings analysis using Python and Google BigQuery");
-                });
-
-                // Rusty graph coloring
-                ui.vertical(|ui| {
-                    ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading2".into()));
-                    ui.hyperlink_to("Rusty Graph Coloring", "https://github.com/ostenloo/rusty-graph-coloring");
-                    ui.add_space(8.0);
-                    ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
-                    ui.label("Graph coloring algorithms implemented in Rust");
-                });
-
-                // Personal Site
-                ui.vertical(|ui| {
-                    ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading2".into()));
-                    ui.hyperlink_to("Personal Site", "https://github.com/ostenloo/egui-personal-site");
-                    ui.add_space(8.0);
-                    ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
-                    ui.label("Built with egui and Rust WebAssembly");
-                });
-            });
-        });
+    fn nav_item(&mut self, ui: &mut egui::Ui, item: &NavItem) -> egui::Response {
+        let is_selected = item.is_active(&self.current_page);
+        let response = ui.add(NavBarButton::new(&item.display_label(), is_selected));
+        if response.clicked() {
+            self.follow_nav_item(ui.ctx(), item);
+        }
+        response
     }
 
-    fn show_blog(&mut self, ui: &mut egui::Ui) {
-        if let Some(blog_index) = self.selected_blog {
-            if let Some(blog_post) = self.blog_posts.get(blog_index) {
-                let mut back_to_list = false;
-
-                ui.add_space(16.0); // Top margin
-
-                let screen_width = ui.available_width();
-                let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
-                let show_dates = screen_width >= 520.0;
-
-                ui.horizontal(|ui| {
-                    ui.add_space(left_margin); // Responsive left margin
-
-                    ui.vertical(|ui| {
-                        let content_width = ui.available_width() - right_margin;
-                        ui.set_max_width(content_width);
-
-                        if ui.button("< Back to Blog List").clicked() {
-                            back_to_list = true;
-                        }
-
-                        ui.add_space(32.0);
-
-                        ui.style_mut().override_text_style =
-                            Some(egui::TextStyle::Name("Heading1".into()));
-                        ui.label(&blog_post.title);
-
-                        ui.add_space(12.0);
-
-                        if show_dates {
-                            ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
-                            ui.colored_label(
-                                egui::Color32::from_rgb(120, 120, 120),
-                                &blog_post.date_display,
-                            );
-                        }
-
-                        ui.add_space(40.0);
-
-                        ui.spacing_mut().item_spacing.y = 20.0;
-                        ui.spacing_mut().indent = 24.0;
-
-                        let viewer_id = format!("blog_{}", blog_post.slug.as_str());
-                        ui.scope(|ui| {
-                            ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
-                            CommonMarkViewer::new(viewer_id).show(
-                                ui,
-                                &mut self.markdown_cache,
-                                &blog_post.content,
-                            );
-                        });
-
-                        ui.add_space(60.0);
-                    });
-                });
+    fn nav_item_full_width(&mut self, ui: &mut egui::Ui, item: &NavItem) -> egui::Response {
+        let is_selected = item.is_active(&self.current_page);
+        let response = ui.add(NavMenuButton::new(&item.display_label(), is_selected));
+        if response.clicked() {
+            self.follow_nav_item(ui.ctx(), item);
+        }
+        response
+    }
 
-                if back_to_list {
-                    self.selected_blog = None;
-                    self.current_page = Page::Blog;
+    fn follow_nav_item(&mut self, ctx: &egui::Context, item: &NavItem) {
+        match &item.target {
+            NavTarget::Page(page) => {
+                if self.navigate_to(page.clone()) {
                     self.push_route_to_browser();
                 }
-            } else {
-                self.selected_blog = None;
             }
-        } else {
-            ui.add_space(24.0); // Top margin for blog list
-
-            let screen_width = ui.available_width();
-            let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
-            let show_dates = screen_width >= 520.0;
-
-            ui.horizontal(|ui| {
-                ui.add_space(left_margin); // Responsive left margin
-
-                ui.vertical(|ui| {
-                    let content_width = ui.available_width() - right_margin;
-                    ui.set_max_width(content_width);
-
-                    ui.style_mut().override_text_style =
-                        Some(egui::TextStyle::Name("Heading1".into()));
-                    ui.label("Blog");
-
-                    ui.add_space(40.0);
-
-                    for index in 0..self.blog_posts.len() {
-                        let blog_post = &self.blog_posts[index];
-                        let title = blog_post.title.clone();
-                        let slug = blog_post.slug.clone();
-                        let date_display = blog_post.date_display.clone();
-                        let mut open_post = false;
-
-                        egui::Frame::group(&ui.style())
-                            .stroke(egui::Stroke::NONE)
-                            .rounding(egui::Rounding::ZERO)
-                            .show(ui, |ui| {
-                                ui.set_width(ui.available_width());
-                                ui.vertical(|ui| {
-                                    ui.add_space(12.0);
-
-                                    ui.horizontal(|ui| {
-                                        ui.vertical(|ui| {
-                                            ui.style_mut().override_text_style =
-                                                Some(egui::TextStyle::Name("Heading2".into()));
-                                            if ui.link(&title).clicked() {
-                                                open_post = true;
-                                            }
-                                        });
-
-                                        if show_dates {
-                                            ui.with_layout(
-                                                egui::Layout::right_to_left(egui::Align::TOP),
-                                                |ui| {
-                                                    ui.add_space(20.0);
-                                                    ui.style_mut().override_text_style =
-                                                        Some(egui::TextStyle::Small);
-                                                    ui.colored_label(
-                                                        egui::Color32::from_rgb(120, 120, 120),
-                                                        &date_display,
-                                                    );
-                                                },
-                                            );
-                                        }
-                                    });
-
-                                    ui.add_space(16.0);
-                                });
-                            });
-
-                        ui.add_space(16.0);
-
-                        if open_post {
-                            self.selected_blog = Some(index);
-                            self.current_page = Page::BlogPost(slug);
-                            self.push_route_to_browser();
-                        }
-                    }
+            NavTarget::External(url) => ctx.open_url(egui::OpenUrl::new_tab(url)),
+        }
+    }
 
-                    ui.add_space(40.0);
-                });
-            });
+    /// Headings of the post or page being shown, or none if it has too few
+    /// for a table of contents.
+    fn table_of_contents(&self) -> Vec<Heading> {
+        let outline = sections::section_for(&self.current_page)
+            .and_then(|section| section.outline(self, &self.current_page));
+        match outline {
+            Some(outline) if outline.headings.len() >= TOC_MIN_HEADINGS => outline.headings.clone(),
+            _ => Vec::new(),
         }
     }
 
-    fn show_private_blog(&mut self, ui: &mut egui::Ui) {
-        // Check if authenticated
-        if !self.is_private_authenticated {
-            // Show password prompt
-            ui.add_space(24.0);
-            
-            let screen_width = ui.available_width();
-            let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
-            
-            ui.horizontal(|ui| {
-                ui.add_space(left_margin);
-                
-                ui.vertical(|ui| {
-                    let content_width = ui.available_width() - right_margin;
-                    ui.set_max_width(content_width);
-                    
-                    ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
-                    ui.label("Private Blog");
-                    
-                    ui.add_space(40.0);
-                    
-                    ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
-                    ui.label("This section is password protected.");
-                    
-                    ui.add_space(20.0);
-                    
-                    ui.horizontal(|ui| {
-                        ui.label("Password:");
-                        let response = ui.add(
-                            egui::TextEdit::singleline(&mut self.password_input)
-                                .password(true)
-                                .desired_width(200.0)
-                        );
-                        
-                        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                            if self.password_input == PRIVATE_BLOG_PASSWORD {
-                                self.is_private_authenticated = true;
-                                self.password_input.clear();
-                            } else {
-                                self.password_input.clear();
-                            }
-                        }
-                        
-                        if ui.button("Submit").clicked() {
-                            if self.password_input == PRIVATE_BLOG_PASSWORD {
-                                self.is_private_authenticated = true;
-                                self.password_input.clear();
-                            } else {
-                                self.password_input.clear();
-                            }
-                        }
-                    });
-                    
-                    ui.add_space(40.0);
-                });
-            });
-            
+    /// The table of contents as a side panel, for wide windows.
+    fn show_table_of_contents_panel(&mut self, ctx: &egui::Context) {
+        let headings = self.table_of_contents();
+        if headings.is_empty() {
             return;
         }
-        
-        // Authenticated - show private blog content
-        if let Some(blog_index) = self.selected_private_blog {
-            if let Some(blog_post) = self.private_blog_posts.get(blog_index) {
-                let mut back_to_list = false;
-
-                ui.add_space(16.0);
-
-                let screen_width = ui.available_width();
-                let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
-                let show_dates = screen_width >= 520.0;
-
-                ui.horizontal(|ui| {
-                    ui.add_space(left_margin);
-
-                    ui.vertical(|ui| {
-                        let content_width = ui.available_width() - right_margin;
-                        ui.set_max_width(content_width);
-
-                        if ui.button("< Back to Private Blog List").clicked() {
-                            back_to_list = true;
-                        }
-
-                        ui.add_space(32.0);
-
-                        ui.style_mut().override_text_style =
-                            Some(egui::TextStyle::Name("Heading1".into()));
-                        ui.label(&blog_post.title);
-
-                        ui.add_space(12.0);
-
-                        if show_dates {
-                            ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
-                            ui.colored_label(
-                                egui::Color32::from_rgb(120, 120, 120),
-                                &blog_post.date_display,
-                            );
-                        }
-
-                        ui.add_space(40.0);
 
-                        ui.spacing_mut().item_spacing.y = 20.0;
-                        ui.spacing_mut().indent = 24.0;
-
-                        let viewer_id = format!("private_blog_{}", blog_post.slug.as_str());
-                        ui.scope(|ui| {
-                            ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
-                            CommonMarkViewer::new(viewer_id).show(
-                                ui,
-                                &mut self.markdown_cache,
-                                &blog_post.content,
-                            );
+        let mut open_anchor = None;
+        egui::SidePanel::right("table_of_contents")
+            .resizable(false)
+            .default_width(240.0)
+            .show(ctx, |ui| {
+                ui.add_space(24.0);
+                egui::CollapsingHeader::new("Contents")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            open_anchor = Self::table_of_contents_entries(ui, &headings);
                         });
-
-                        ui.add_space(60.0);
                     });
-                });
-
-                if back_to_list {
-                    self.selected_private_blog = None;
-                    self.current_page = Page::PrivateBlog;
-                    self.push_route_to_browser();
-                }
-            } else {
-                self.selected_private_blog = None;
-            }
-        } else {
-            // Show private blog list
-            ui.add_space(24.0);
-
-            let screen_width = ui.available_width();
-            let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
-            let show_dates = screen_width >= 520.0;
-
-            ui.horizontal(|ui| {
-                ui.add_space(left_margin);
+            });
 
-                ui.vertical(|ui| {
-                    let content_width = ui.available_width() - right_margin;
-                    ui.set_max_width(content_width);
+        if let Some(anchor) = open_anchor {
+            self.go_to_anchor(anchor);
+        }
+    }
 
-                    ui.style_mut().override_text_style =
-                        Some(egui::TextStyle::Name("Heading1".into()));
-                    ui.label("Private Blog");
-
-                    ui.add_space(40.0);
-
-                    for index in 0..self.private_blog_posts.len() {
-                        let blog_post = &self.private_blog_posts[index];
-                        let title = blog_post.title.clone();
-                        let slug = blog_post.slug.clone();
-                        let date_display = blog_post.date_display.clone();
-                        let mut open_post = false;
-
-                        egui::Frame::group(&ui.style())
-                            .stroke(egui::Stroke::NONE)
-                            .rounding(egui::Rounding::ZERO)
-                            .show(ui, |ui| {
-                                ui.set_width(ui.available_width());
-                                ui.vertical(|ui| {
-                                    ui.add_space(12.0);
-
-                                    ui.horizontal(|ui| {
-                                        ui.vertical(|ui| {
-                                            ui.style_mut().override_text_style =
-                                                Some(egui::TextStyle::Name("Heading2".into()));
-                                            if ui.link(&title).clicked() {
-                                                open_post = true;
-                                            }
-                                        });
-
-                                        if show_dates {
-                                            ui.with_layout(
-                                                egui::Layout::right_to_left(egui::Align::TOP),
-                                                |ui| {
-                                                    ui.add_space(20.0);
-                                                    ui.style_mut().override_text_style =
-                                                        Some(egui::TextStyle::Small);
-                                                    ui.colored_label(
-                                                        egui::Color32::from_rgb(120, 120, 120),
-                                                        &date_display,
-                                                    );
-                                                },
-                                            );
-                                        }
-                                    });
-
-                                    ui.add_space(16.0);
-                                });
-                            });
-
-                        ui.add_space(16.0);
-
-                        if open_post {
-                            self.selected_private_blog = Some(index);
-                            self.current_page = Page::PrivateBlogPost(slug);
-                            self.push_route_to_browser();
-                        }
-                    }
+    /// The table of contents as a dropdown above the text, for windows too
+    /// narrow for the side panel. Returns the anchor of a clicked heading.
+    fn contents_dropdown(ui: &mut egui::Ui, headings: &[Heading]) -> Option<String> {
+        if ui.ctx().screen_rect().width() >= TOC_PANEL_MIN_WIDTH {
+            return None;
+        }
+        if headings.len() < TOC_MIN_HEADINGS {
+            return None;
+        }
 
-                    ui.add_space(40.0);
-                });
+        let mut open_anchor = None;
+        ui.add_space(12.0);
+        ui.scope(|ui| {
+            ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+            ui.menu_button("Contents ▾", |ui| {
+                if let Some(anchor) = Self::table_of_contents_entries(ui, headings) {
+                    open_anchor = Some(anchor);
+                    ui.close_menu();
+                }
             });
-        }
+        });
+        open_anchor
     }
 
-    fn create_sample_blog_posts() -> Vec<BlogPost> {
-        let mut posts = Vec::new();
-
-        // Automatically discover all .md files in the blog_posts directory
-        for file in BLOG_POSTS_DIR.files() {
-            if let Some(extension) = file.path().extension() {
-                if extension == "md" {
-                    if let Ok(content) = std::str::from_utf8(file.contents()) {
-                        let slug = file
-                            .path()
-                            .file_stem()
-                            .map(|stem| stem.to_string_lossy().to_string())
-                            .unwrap_or_default();
-                        posts.push(Self::parse_blog_post(content, slug));
-                    }
+    /// One link per heading, indented by level. Returns the anchor of the
+    /// one clicked.
+    fn table_of_contents_entries(ui: &mut egui::Ui, headings: &[Heading]) -> Option<String> {
+        let top_level = headings.iter().map(|heading| heading.level).min()?;
+        let mut open_anchor = None;
+        ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+        ui.spacing_mut().item_spacing.y = 6.0;
+        for heading in headings {
+            ui.horizontal(|ui| {
+                ui.add_space(f32::from(heading.level - top_level) * 12.0);
+                if ui.link(&heading.text).clicked() {
+                    open_anchor = Some(heading.anchor.clone());
                 }
-            }
+            });
         }
-
-        // Sort posts by published timestamp (newest first)
-        posts.sort_by(|a, b| b.published_at.cmp(&a.published_at));
-
-        posts
+        open_anchor
     }
 
-    fn create_private_blog_posts() -> Vec<BlogPost> {
-        let mut posts = Vec::new();
+    /// Scroll to the heading anchored `anchor` and put it in the URL.
+    fn go_to_anchor(&mut self, anchor: String) {
+        self.route = Route {
+            fragment: Some(anchor),
+            ..self.current_route()
+        };
+        self.scroll_to_fragment = true;
+        self.push_route_to_browser();
+    }
 
-        // Automatically discover all .md files in the private_blog_posts directory
-        for file in PRIVATE_BLOG_POSTS_DIR.files() {
-            if let Some(extension) = file.path().extension() {
-                if extension == "md" {
-                    if let Ok(content) = std::str::from_utf8(file.contents()) {
-                        let slug = file
-                            .path()
-                            .file_stem()
-                            .map(|stem| stem.to_string_lossy().to_string())
-                            .unwrap_or_default();
-                        posts.push(Self::parse_blog_post(content, slug));
-                    }
-                }
+    /// Draw a document one piece of its `outline` at a time (see
+    /// `headings::split_at_headings`), so that the piece holding the heading
+    /// anchored `scroll_to` can be scrolled into view.
+    fn show_markdown_sections(
+        ui: &mut egui::Ui,
+        cache: &mut CommonMarkCache,
+        viewer_id: &str,
+        outline: &Outline,
+        scroll_to: Option<&str>,
+    ) {
+        let target = outline
+            .headings
+            .iter()
+            .position(|heading| Some(heading.anchor.as_str()) == scroll_to);
+        for (index, part) in outline.parts.iter().enumerate() {
+            let response = CommonMarkViewer::new(format!("{}_{}", viewer_id, index))
+                .show(ui, cache, &part.markdown)
+                .response;
+            if target.is_some_and(|target| part.headings.contains(&target)) {
+                response.scroll_to_me(Some(egui::Align::TOP));
             }
         }
+    }
 
-        // Sort posts by published timestamp (newest first)
-        posts.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+    fn embedded_files(
+        dir: &'static Dir<'static>,
+    ) -> impl Iterator<Item = (&'static str, &'static str)> {
+        dir.files().filter_map(|file| {
+            let name = file.path().to_str()?;
+            let content = std::str::from_utf8(file.contents()).ok()?;
+            Some((name, content))
+        })
+    }
 
-        posts
+    /// Let the current page's section catch up with it (see `Section::sync`).
+    fn sync_section_from_route(&mut self) {
+        let page = self.current_page.clone();
+        if let Some(section) = sections::section_for(&page) {
+            section.sync(self, &page);
+        }
     }
 
-    fn parse_blog_post(content: &str, mut slug: String) -> BlogPost {
-        let (frontmatter, body) = if let Some(stripped) = content.strip_prefix("---\n") {
-            // Find the end of frontmatter
-            if let Some(end_pos) = stripped.find("\n---\n") {
-                let frontmatter = &stripped[..end_pos];
-                let body = &stripped[end_pos + 5..]; // Skip "\n---\n"
-                (Some(frontmatter), body)
-            } else {
-                (None, content)
+    /// Follow the browser's back and forward buttons: show the page now in
+    /// the address bar, scrolled to where the visitor left it.
+    fn follow_history_navigation(&mut self) {
+        #[cfg(target_arch = "wasm32")]
+        if let Some(state) = POPPED_STATE.with(|popped| popped.borrow_mut().take()) {
+            self.pull_route_from_browser();
+            // Entries the app didn't create, e.g. a fragment typed into the
+            // address bar, start at the top or at their heading instead.
+            if let Some(state) = state {
+                self.scroll_to_fragment = false;
+                self.scrolled_page = self.current_page.clone();
+                self.scroll_offset = state.scroll;
+                self.recorded_scroll = state.scroll;
+                self.restore_scroll = Some(state.scroll);
             }
-        } else {
-            (None, content)
-        };
-
-        let mut title = "Untitled".to_string();
-        let mut date_display = "Unknown".to_string();
-        let mut published_at = Utc.from_utc_datetime(
-            &NaiveDate::from_ymd_opt(1970, 1, 1)
-                .unwrap()
-                .and_hms_opt(0, 0, 0)
-                .unwrap(),
-        );
+        }
+    }
 
-        if let Some(fm) = frontmatter {
-            for line in fm.lines() {
-                let line = line.trim();
-                if let Some(rest) = line.strip_prefix("title:") {
-                    title = rest.trim().trim_matches('"').to_string();
-                } else if let Some(rest) = line.strip_prefix("date:") {
-                    let value = rest.trim().trim_matches('"');
-                    date_display = value.to_string();
-
-                    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
-                        date_display = dt.format("%B %-d, %Y").to_string();
-                        published_at = dt.with_timezone(&Utc);
-                    } else if let Ok(dt) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
-                    {
-                        date_display = dt.format("%B %-d, %Y").to_string();
-                        published_at = Utc.from_utc_datetime(&dt);
-                    } else if let Ok(dt) = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
-                    {
-                        date_display = dt.format("%B %-d, %Y").to_string();
-                        published_at = Utc.from_utc_datetime(&dt);
-                    } else if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
-                        date_display = date.format("%B %-d, %Y").to_string();
-                        published_at = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap());
-                    } else if let Ok(date) = NaiveDate::parse_from_str(value, "%B %d, %Y") {
-                        date_display = date.format("%B %-d, %Y").to_string();
-                        published_at = Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap());
-                    }
-                } else if let Some(rest) = line.strip_prefix("slug:") {
-                    let candidate = rest.trim().trim_matches('"').to_string();
-                    if !candidate.is_empty() {
-                        slug = candidate;
-                    }
-                }
+    /// Keep the current history entry's scroll position up to date, writing
+    /// it once scrolling settles rather than on every frame.
+    #[cfg_attr(not(target_arch = "wasm32"), allow(unused_variables))]
+    fn track_scroll(&mut self, ctx: &egui::Context, offset: f32) {
+        if offset != self.scroll_offset {
+            self.scroll_offset = offset;
+            // Look again next frame to see whether it has settled.
+            ctx.request_repaint();
+        } else if offset != self.recorded_scroll {
+            self.recorded_scroll = offset;
+            #[cfg(target_arch = "wasm32")]
+            if let Some(history) = web_sys::window().and_then(|window| window.history().ok()) {
+                let _ = history.replace_state(&HistoryState { scroll: offset }.to_js(), "");
             }
         }
+    }
 
-        BlogPost {
-            title,
-            date_display,
-            content: body.to_string(),
-            slug,
-            published_at,
+    /// The current page with the query parameters and fragment it was
+    /// opened with, which are dropped once the visitor moves on.
+    fn current_route(&self) -> Route {
+        if self.route.page == self.current_page {
+            self.route.clone()
+        } else {
+            Route::from(self.current_page.clone())
         }
     }
 
-    fn sync_blog_selection_from_route(&mut self) {
-        match self.current_page.clone() {
-            Page::BlogPost(slug) => {
-                if let Some(index) = self.blog_posts.iter().position(|post| post.slug == slug) {
-                    self.selected_blog = Some(index);
-                } else {
-                    self.selected_blog = None;
-                    self.current_page = Page::Blog;
-                    self.push_route_to_browser();
-                }
-            }
-            Page::Blog => {
-                self.selected_blog = None;
-            }
-            Page::PrivateBlogPost(slug) => {
-                if let Some(index) = self.private_blog_posts.iter().position(|post| post.slug == slug) {
-                    self.selected_private_blog = Some(index);
-                } else {
-                    self.selected_private_blog = None;
-                    self.current_page = Page::PrivateBlog;
-                    self.push_route_to_browser();
-                }
-            }
-            Page::PrivateBlog => {
-                self.selected_private_blog = None;
-            }
-            _ => {}
+    /// The heading to scroll to, once, after opening a URL with a fragment.
+    fn take_scroll_anchor(&mut self) -> Option<String> {
+        if std::mem::take(&mut self.scroll_to_fragment) {
+            self.current_route().fragment
+        } else {
+            None
         }
     }
 
     fn pull_route_from_browser(&mut self) {
         #[cfg(target_arch = "wasm32")]
         {
-            if let Some(path) = current_pathname() {
-                let new_page = Page::from_path(&path);
-                if new_page != self.current_page {
-                    self.current_page = new_page;
+            if let Some(url) = browser_route() {
+                let route = Route::from_url(&url);
+                if route.page != self.current_page {
+                    self.current_page = route.page.clone();
                 }
+                self.scroll_to_fragment = route.fragment.is_some();
+                self.route = route;
             }
         }
     }
 
-    fn push_route_to_browser(&self) {
+    fn push_route_to_browser(&mut self) {
+        self.write_route_to_browser(false);
+    }
+
+    /// Like `push_route_to_browser`, but updates the current history entry
+    /// instead of adding one (e.g. while typing into the search box).
+    fn replace_route_in_browser(&mut self) {
+        self.write_route_to_browser(true);
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), allow(unused_variables))]
+    fn write_route_to_browser(&mut self, replace: bool) {
+        self.route = self.current_route();
+
         #[cfg(target_arch = "wasm32")]
         {
-            let desired_path = self.current_page.to_url();
+            let desired_path = self.route.to_url();
 
-            if let Some(current_path) = current_pathname() {
+            if let Some(current_path) = browser_route() {
                 if current_path.as_str() == desired_path.as_str() {
                     return;
                 }
             }
+            let desired_path = RoutingMode::embedded().href(&desired_path);
 
             if let Some(window) = web_sys::window() {
                 if let Ok(history) = window.history() {
-                    let _ = history.push_state_with_url(
-                        &wasm_bindgen::JsValue::NULL,
-                        "",
-                        Some(&desired_path),
-                    );
+                    // The new page starts at the top.
+                    let state = HistoryState { scroll: 0.0 }.to_js();
+                    let _ = if replace {
+                        history.replace_state_with_url(&state, "", Some(&desired_path))
+                    } else {
+                        // Remember how far down the page being left was, for
+                        // when the visitor comes back to it.
+                        let left = HistoryState {
+                            scroll: self.scroll_offset,
+                        };
+                        let _ = history.replace_state(&left.to_js(), "");
+                        history.push_state_with_url(&state, "", Some(&desired_path))
+                    };
                 }
             }
         }
     }
 }
 
+/// What the app keeps in each browser history entry.
+#[cfg(target_arch = "wasm32")]
+struct HistoryState {
+    /// Vertical scroll of the page, in points.
+    scroll: f32,
+}
+
 #[cfg(target_arch = "wasm32")]
-fn current_pathname() -> Option<String> {
-    let window = web_sys::window()?;
-    window.location().pathname().ok()
+impl HistoryState {
+    const SCROLL: &'static str = "scroll";
+
+    fn to_js(&self) -> wasm_bindgen::JsValue {
+        let state = js_sys::Object::new();
+        let _ = js_sys::Reflect::set(&state, &Self::SCROLL.into(), &self.scroll.into());
+        state.into()
+    }
+
+    /// `None` for entries the app didn't create, such as the first page load.
+    fn from_js(value: &wasm_bindgen::JsValue) -> Option<Self> {
+        let scroll = js_sys::Reflect::get(value, &Self::SCROLL.into()).ok()?;
+        Some(Self {
+            scroll: scroll.as_f64()? as f32,
+        })
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    /// Set by the `popstate` listener: the state of the history entry the
+    /// browser moved to, if any, until the next frame picks it up.
+    static POPPED_STATE: std::cell::RefCell<Option<Option<HistoryState>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// The state of the current history entry.
+#[cfg(target_arch = "wasm32")]
+fn history_state() -> Option<HistoryState> {
+    let state = web_sys::window()?.history().ok()?.state().ok()?;
+    HistoryState::from_js(&state)
+}
+
+/// Hand back and forward navigation to the app, which would otherwise not
+/// notice until the next input event.
+#[cfg(target_arch = "wasm32")]
+fn listen_for_history_navigation(ctx: &egui::Context) {
+    use wasm_bindgen::JsCast as _;
+
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let ctx = ctx.clone();
+    let on_popstate = wasm_bindgen::closure::Closure::<dyn FnMut(web_sys::PopStateEvent)>::new(
+        move |event: web_sys::PopStateEvent| {
+            let state = HistoryState::from_js(&event.state());
+            POPPED_STATE.with(|popped| *popped.borrow_mut() = Some(state));
+            ctx.request_repaint();
+        },
+    );
+    let _ =
+        window.add_event_listener_with_callback("popstate", on_popstate.as_ref().unchecked_ref());
+    // The listener lives as long as the page.
+    on_popstate.forget();
+}
+
+/// The route in the address bar, e.g. `/blog/search?q=adhd` or
+/// `/blog/my-post#why-rust`, from the path or the fragment depending on the
+/// routing mode.
+#[cfg(target_arch = "wasm32")]
+fn browser_route() -> Option<String> {
+    let location = web_sys::window()?.location();
+    let path = location.pathname().ok()?;
+    let search = location.search().unwrap_or_default();
+    let hash = location.hash().unwrap_or_default();
+    Some(RoutingMode::embedded().route(&path, &search, &hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routing::RoutingMode;
+
+    /// What `window.location` holds after the app writes `href` there: path,
+    /// query (with its `?`) and fragment (with its `#`).
+    fn location(href: &str) -> (String, String, String) {
+        let (rest, fragment) = match href.split_once('#') {
+            Some((rest, fragment)) => (rest, format!("#{}", fragment)),
+            None => (href, String::new()),
+        };
+        let (path, query) = match rest.split_once('?') {
+            Some((path, query)) => (path, format!("?{}", query)),
+            None => (rest, String::new()),
+        };
+        let path = if path.is_empty() { "/" } else { path };
+        (path.to_string(), query, fragment)
+    }
+
+    #[test]
+    fn routes_round_trip_in_every_routing_mode() {
+        let urls = [
+            "/",
+            "/projects?tech=rust&sort=name",
+            "/projects/zeitgus",
+            "/blog?page=2#intro",
+            "/blog/tag/rust",
+            "/blog/tag/%E6%97%A5%E6%9C%AC",
+            "/blog/search?q=rust+wasm&page=2",
+            "/blog/stats",
+            "/blog/my-post#why-rust",
+            "/private/secret",
+            "/nope?a=1",
+        ];
+        for mode in RoutingMode::ALL {
+            for url in urls {
+                let route = Route::from_url(url);
+                let (path, query, fragment) = location(&mode.href(&route.to_url()));
+                let back = Route::from_url(&mode.route(&path, &query, &fragment));
+                assert_eq!(back, route, "{} in {} mode", url, mode.name());
+                assert_eq!(back.to_url(), url, "{} in {} mode", url, mode.name());
+            }
+        }
+    }
+
+    #[test]
+    fn routes_split_query_and_fragment_from_the_page() {
+        let route = Route::from_url("/blog/search?q=a+b&page=2#results");
+        assert_eq!(route.page, Page::BlogSearch("a b".to_string()));
+        assert_eq!(route.query, [("page".to_string(), "2".to_string())]);
+        assert_eq!(route.fragment.as_deref(), Some("results"));
+
+        let route = Route::from_url("/blog/tag/Web%20Dev");
+        assert_eq!(route.page, Page::BlogTag("web-dev".to_string()));
+
+        let route = Route::from_url("/blog/tag/%E6%97%A5%E6%9C%AC");
+        assert_eq!(
+            route.page,
+            Page::NotFound("/blog/tag/%E6%97%A5%E6%9C%AC".to_string())
+        );
+    }
+
+    #[test]
+    fn nav_lists_each_page_marked_nav_once() {
+        let nav = MyApp::nav_registry();
+        for page in MarkdownPage::embedded().iter().filter(|page| page.meta.nav) {
+            let target = NavTarget::Page(Page::Section(page.slug.clone()));
+            let listed = nav.visible(false).filter(|item| item.target == target);
+            assert_eq!(listed.count(), 1, "{}", page.slug);
+        }
+    }
 }
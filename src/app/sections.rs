@@ -0,0 +1,115 @@
+//! The sections of the site and the router that dispatches to them.
+//!
+//! A section claims some of the site's paths (`/projects`,
+//! `/projects/{slug}`, ...) and knows how to turn them into a [`Page`], what
+//! to call the page and how to draw it. Each lives in a module of its own
+//! below this one, with its drawing code and the state only it uses. Adding
+//! a section, say a "Reading list", means a `reading` module implementing
+//! [`Section`] and listing it in [`SECTIONS`]; routing, URLs, the window
+//! title, the table of contents and the nav pick it up from there. Sections
+//! without a `Page` variant of their own use [`Page::Section`].
+
+use super::{MyApp, Page};
+use crate::headings::Outline;
+use crate::nav::NavItem;
+use crate::site;
+
+mod blog;
+mod home;
+mod not_found;
+mod pages;
+mod private;
+mod projects;
+
+pub(super) use blog::BlogState;
+pub(super) use private::PrivateBlogState;
+pub use projects::ProjectFilter;
+pub(super) use projects::ProjectsState;
+
+pub trait Section: Sync {
+    /// The page for a path, given as its non-empty segments and the raw
+    /// query string, or `None` if the path isn't one of this section's.
+    fn route(&self, segments: &[&str], query: &str) -> Option<Page>;
+
+    /// Whether `page` is one of this section's pages.
+    fn owns(&self, page: &Page) -> bool;
+
+    /// The path of one of this section's pages, the inverse of
+    /// [`Section::route`].
+    fn to_url(&self, page: &Page) -> String;
+
+    /// Whether `page` keeps query parameter `key` itself and writes it back
+    /// in [`Section::to_url`]. Other parameters stay in the `Route`.
+    fn keeps_query_param(&self, _page: &Page, _key: &str) -> bool {
+        false
+    }
+
+    /// Whether `page` is `root` or one of its sub-pages, for highlighting
+    /// the nav entry that links to `root`. Both are this section's pages.
+    fn is_within(&self, page: &Page, root: &Page) -> bool {
+        page == root
+    }
+
+    /// Bring the app in line with `page` once it is the current page, e.g.
+    /// select the post it names, or show [`Page::NotFound`] if there is no
+    /// such post.
+    fn sync(&self, _app: &mut MyApp, _page: &Page) {}
+
+    /// What the window or tab is called while `page` is shown, before the
+    /// site name. `None` shows just the site name.
+    fn title(&self, app: &MyApp, page: &Page) -> Option<String>;
+
+    /// The headings of the post or page `page` shows, for the table of
+    /// contents.
+    fn outline<'a>(&self, _app: &'a MyApp, _page: &Page) -> Option<&'a Outline> {
+        None
+    }
+
+    /// Entries for the top navigation, each added unless `site.toml` already
+    /// links to the same page.
+    fn nav_items(&self) -> Vec<NavItem> {
+        Vec::new()
+    }
+
+    fn show(&self, app: &mut MyApp, ui: &mut egui::Ui, page: &Page);
+}
+
+/// Every section, in routing order: the first to claim a path gets it.
+pub(super) static SECTIONS: &[&dyn Section] = &[
+    &home::Home,
+    &projects::Projects,
+    &blog::Blog,
+    &private::PrivateBlog,
+    &pages::MarkdownPages,
+    &not_found::NotFound,
+];
+
+/// The page for a path such as `/blog/tag/rust` or `/projects?tech=rust`,
+/// or [`Page::NotFound`] if no section claims it.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+pub(super) fn route(path: &str) -> Page {
+    let requested = path.trim();
+    let (path, query) = requested.split_once('?').unwrap_or((requested, ""));
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    SECTIONS
+        .iter()
+        .find_map(|section| section.route(&segments, query))
+        .unwrap_or_else(|| Page::NotFound(format!("/{}", requested.trim_start_matches('/'))))
+}
+
+pub(super) fn section_for(page: &Page) -> Option<&'static dyn Section> {
+    SECTIONS.iter().copied().find(|section| section.owns(page))
+}
+
+/// The window or tab title for `page`, e.g. "Blog · Austin Liu".
+pub(super) fn title(app: &MyApp, page: &Page) -> String {
+    let site_name = &site::embedded().title;
+    match section_for(page).and_then(|section| section.title(app, page)) {
+        Some(title) => format!("{} · {}", title, site_name),
+        None => site_name.clone(),
+    }
+}
@@ -0,0 +1,1072 @@
+//! The blog: the post list, tags, search, stats and the posts themselves.
+
+use chrono::{DateTime, Utc};
+use include_dir::{include_dir, Dir};
+
+use super::Section;
+use crate::app::{
+    decode_query_value, encode_query_value, query_param, BlogPost, MyApp, Page, TagChip,
+};
+use crate::frontmatter::tag_slug;
+use crate::headings::Outline;
+use crate::search::{self, SearchIndex};
+use crate::stats::{self, Totals};
+
+// Staged by build.rs: validated copies of `blog_posts/`, without drafts in web release builds.
+static BLOG_POSTS_DIR: Dir<'_> = include_dir!("$OUT_DIR/blog_posts");
+
+pub(super) struct Blog;
+
+impl Section for Blog {
+    fn route(&self, segments: &[&str], query: &str) -> Option<Page> {
+        let ["blog", rest @ ..] = segments else {
+            return None;
+        };
+        // The named views win over `[slug]`; build.rs keeps posts off their
+        // names (`pages::RESERVED_POST_SLUGS`).
+        match rest {
+            [] => Some(Page::Blog),
+            ["tag"] => Some(Page::BlogTags),
+            // Tags are stored normalised, so `/blog/tag/Web%20Dev` is
+            // `web-dev`. One with nothing left, e.g. all non-ASCII, is no
+            // tag at all.
+            ["tag", tag] => {
+                let tag = tag_slug(&decode_query_value(tag));
+                (!tag.is_empty()).then_some(Page::BlogTag(tag))
+            }
+            ["search"] => Some(Page::BlogSearch(
+                query_param(query, "q").unwrap_or_default(),
+            )),
+            ["stats"] => Some(Page::BlogStats),
+            [slug] => Some(Page::BlogPost(slug.to_string())),
+            _ => None,
+        }
+    }
+
+    fn owns(&self, page: &Page) -> bool {
+        matches!(
+            page,
+            Page::Blog
+                | Page::BlogPost(_)
+                | Page::BlogTags
+                | Page::BlogTag(_)
+                | Page::BlogSearch(_)
+                | Page::BlogStats
+        )
+    }
+
+    fn to_url(&self, page: &Page) -> String {
+        match page {
+            Page::BlogPost(slug) => format!("/blog/{}", slug),
+            Page::BlogTags => "/blog/tag".to_string(),
+            Page::BlogTag(tag) => format!("/blog/tag/{}", tag),
+            Page::BlogSearch(query) => format!("/blog/search?q={}", encode_query_value(query)),
+            Page::BlogStats => "/blog/stats".to_string(),
+            _ => "/blog".to_string(),
+        }
+    }
+
+    fn keeps_query_param(&self, page: &Page, key: &str) -> bool {
+        matches!(page, Page::BlogSearch(_)) && key == "q"
+    }
+
+    fn is_within(&self, page: &Page, root: &Page) -> bool {
+        *root == Page::Blog || page == root
+    }
+
+    /// Selects the post a `/blog/{slug}` names, and mirrors a search's query
+    /// into the search box.
+    fn sync(&self, app: &mut MyApp, page: &Page) {
+        app.blog.selected = None;
+        match page {
+            Page::BlogPost(slug) => {
+                match app.blog.posts.iter().position(|post| &post.slug == slug) {
+                    Some(index) => app.blog.selected = Some(index),
+                    None => app.current_page = Page::NotFound(page.to_url()),
+                }
+            }
+            Page::Blog => app.blog.search_query.clear(),
+            Page::BlogSearch(query) if app.blog.search_query != *query => {
+                app.blog.search_query = query.clone();
+            }
+            _ => {}
+        }
+    }
+
+    fn title(&self, app: &MyApp, page: &Page) -> Option<String> {
+        Some(match page {
+            Page::BlogPost(slug) => app
+                .blog
+                .posts
+                .iter()
+                .find(|post| &post.slug == slug)
+                .map_or_else(|| "Blog".to_string(), |post| post.title.clone()),
+            Page::BlogTags => "Tags".to_string(),
+            Page::BlogTag(tag) => format!("#{}", tag),
+            Page::BlogSearch(query) if !query.is_empty() => format!("Search: {}", query),
+            Page::BlogSearch(_) => "Search".to_string(),
+            Page::BlogStats => "Stats".to_string(),
+            _ => "Blog".to_string(),
+        })
+    }
+
+    fn outline<'a>(&self, app: &'a MyApp, page: &Page) -> Option<&'a Outline> {
+        match page {
+            Page::BlogPost(_) => app
+                .blog
+                .selected
+                .and_then(|index| app.blog.posts.get(index))
+                .map(|post| &post.outline),
+            _ => None,
+        }
+    }
+
+    fn show(&self, app: &mut MyApp, ui: &mut egui::Ui, _page: &Page) {
+        app.show_blog(ui);
+    }
+}
+
+/// The published posts and what is derived from them, the posts still
+/// scheduled, and where the visitor is in the blog.
+pub(in crate::app) struct BlogState {
+    pub(in crate::app) posts: Vec<BlogPost>,
+    /// Posts whose `date` is still in the future.
+    scheduled: Vec<BlogPost>,
+    /// Rebuilt whenever `posts` changes.
+    search_index: SearchIndex,
+    /// Indices into `posts`, rebuilt with `search_index`.
+    related: Vec<Vec<usize>>,
+    /// The post a `/blog/{slug}` names.
+    pub(in crate::app) selected: Option<usize>,
+    /// Mirrors the `?q=` of the search route.
+    search_query: String,
+}
+
+impl Default for BlogState {
+    fn default() -> Self {
+        Self::new(
+            BlogPost::load_all(MyApp::embedded_files(&BLOG_POSTS_DIR)),
+            Utc::now(),
+        )
+    }
+}
+
+impl BlogState {
+    /// The blog as of `now`: later posts are held back until they are due.
+    pub(in crate::app) fn new(posts: Vec<BlogPost>, now: DateTime<Utc>) -> Self {
+        let (posts, scheduled) = split_scheduled(posts, now);
+        let search_index = build_search_index(&posts);
+        let related = related_posts(&posts, &search_index);
+        Self {
+            posts,
+            scheduled,
+            search_index,
+            related,
+            selected: None,
+            search_query: String::new(),
+        }
+    }
+}
+
+/// Split posts into those already published at `now` and those scheduled
+/// for later.
+fn split_scheduled(posts: Vec<BlogPost>, now: DateTime<Utc>) -> (Vec<BlogPost>, Vec<BlogPost>) {
+    posts.into_iter().partition(|post| post.published_at <= now)
+}
+
+fn build_search_index(posts: &[BlogPost]) -> SearchIndex {
+    SearchIndex::new(
+        posts
+            .iter()
+            .map(|post| (post.title.as_str(), post.content.as_str())),
+    )
+}
+
+/// What was clicked in a post list row (see `MyApp::post_row`).
+pub(super) enum PostRowClick {
+    Title,
+    Tag(String),
+}
+
+/// How many related posts are listed under a post.
+const RELATED_POSTS: usize = 3;
+
+/// Each shared tag adds this to a related post's score, on top of the 0 to 1
+/// of `SearchIndex::similar`, so sharing a tag outweighs any wording.
+const SHARED_TAG_SCORE: f32 = 1.0;
+
+/// Posts that share no tag and less wording than this aren't related.
+const MIN_RELATED_SCORE: f32 = 0.05;
+
+/// For each post, the posts most related to it, best first: scored by
+/// the tags they share and by how similar their wording is.
+fn related_posts(posts: &[BlogPost], index: &SearchIndex) -> Vec<Vec<usize>> {
+    (0..posts.len())
+        .map(|doc| {
+            let tags = &posts[doc].meta.tags;
+            let mut ranked: Vec<(usize, f32)> = index
+                .similar(doc)
+                .into_iter()
+                .map(|hit| (hit.doc, hit.score))
+                .collect();
+            for (other, post) in posts.iter().enumerate() {
+                let shared = post
+                    .meta
+                    .tags
+                    .iter()
+                    .filter(|tag| tags.contains(tag))
+                    .count();
+                if other == doc || shared == 0 {
+                    continue;
+                }
+                let score = shared as f32 * SHARED_TAG_SCORE;
+                match ranked.iter_mut().find(|(candidate, _)| *candidate == other) {
+                    Some((_, similarity)) => *similarity += score,
+                    None => ranked.push((other, score)),
+                }
+            }
+            ranked.retain(|&(_, score)| score >= MIN_RELATED_SCORE);
+            ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+            ranked
+                .into_iter()
+                .take(RELATED_POSTS)
+                .map(|(other, _)| other)
+                .collect()
+        })
+        .collect()
+}
+
+impl MyApp {
+    fn show_blog(&mut self, ui: &mut egui::Ui) {
+        if let Some(blog_index) = self.blog.selected {
+            let scroll_to = self.take_scroll_anchor();
+            if let Some(blog_post) = self.blog.posts.get(blog_index) {
+                let mut back_to_list = false;
+                let mut open_tag = None;
+                let mut open_anchor = None;
+                let mut open_post = None;
+
+                // `blog.posts` is newest first.
+                let previous = Some(blog_index + 1).filter(|&index| index < self.blog.posts.len());
+                let next = blog_index.checked_sub(1);
+                let related = self
+                    .blog
+                    .related
+                    .get(blog_index)
+                    .cloned()
+                    .unwrap_or_default();
+
+                // Arrow keys page through posts unless a text field wants them.
+                if ui.memory(|memory| memory.focused().is_none()) {
+                    ui.input(|input| {
+                        if input.key_pressed(egui::Key::ArrowLeft) {
+                            open_post = open_post.or(previous);
+                        }
+                        if input.key_pressed(egui::Key::ArrowRight) {
+                            open_post = open_post.or(next);
+                        }
+                    });
+                }
+
+                ui.add_space(16.0); // Top margin
+
+                let screen_width = ui.available_width();
+                let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+                let show_dates = screen_width >= 520.0;
+
+                ui.horizontal(|ui| {
+                    ui.add_space(left_margin); // Responsive left margin
+
+                    ui.vertical(|ui| {
+                        let content_width = ui.available_width() - right_margin;
+                        ui.set_max_width(content_width);
+
+                        if ui.button("< Back to Blog List").clicked() {
+                            back_to_list = true;
+                        }
+
+                        ui.add_space(32.0);
+
+                        ui.style_mut().override_text_style =
+                            Some(egui::TextStyle::Name("Heading1".into()));
+                        ui.label(&blog_post.title);
+
+                        if blog_post.meta.draft {
+                            Self::draft_badge(ui);
+                        }
+
+                        ui.add_space(12.0);
+
+                        if show_dates {
+                            ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                            ui.colored_label(
+                                egui::Color32::from_rgb(120, 120, 120),
+                                format!(
+                                    "{} · {}",
+                                    blog_post.date_display,
+                                    blog_post.stats.reading_time()
+                                ),
+                            );
+                            if let Some(updated) = blog_post.meta.updated {
+                                ui.colored_label(
+                                    egui::Color32::from_rgb(120, 120, 120),
+                                    format!("Updated {}", updated.format("%B %-d, %Y")),
+                                );
+                            }
+                        }
+
+                        if let Some(tag) = Self::tag_chips(ui, &blog_post.meta.tags, None) {
+                            open_tag = Some(tag);
+                        }
+
+                        if let Some(anchor) =
+                            Self::contents_dropdown(ui, &blog_post.outline.headings)
+                        {
+                            open_anchor = Some(anchor);
+                        }
+
+                        ui.add_space(40.0);
+
+                        ui.spacing_mut().item_spacing.y = 20.0;
+                        ui.spacing_mut().indent = 24.0;
+
+                        let viewer_id = format!("blog_{}", blog_post.slug.as_str());
+                        ui.scope(|ui| {
+                            ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                            Self::show_markdown_sections(
+                                ui,
+                                &mut self.markdown_cache,
+                                &viewer_id,
+                                &blog_post.outline,
+                                scroll_to.as_deref(),
+                            );
+                        });
+
+                        ui.add_space(20.0);
+                        ui.separator();
+                        ui.spacing_mut().item_spacing.y = 8.0;
+
+                        if let Some(index) = Self::post_pager(
+                            ui,
+                            previous.map(|index| (index, &self.blog.posts[index])),
+                            next.map(|index| (index, &self.blog.posts[index])),
+                            show_dates,
+                        ) {
+                            open_post = Some(index);
+                        }
+
+                        if !related.is_empty() {
+                            ui.add_space(32.0);
+                            ui.style_mut().override_text_style =
+                                Some(egui::TextStyle::Name("Heading2".into()));
+                            ui.label("Related posts");
+
+                            for &index in &related {
+                                let post = &self.blog.posts[index];
+                                ui.horizontal_wrapped(|ui| {
+                                    ui.style_mut().override_text_style =
+                                        Some(egui::TextStyle::Body);
+                                    if ui.link(&post.title).clicked() {
+                                        open_post = Some(index);
+                                    }
+                                    if show_dates {
+                                        ui.style_mut().override_text_style =
+                                            Some(egui::TextStyle::Small);
+                                        ui.colored_label(
+                                            egui::Color32::from_rgb(120, 120, 120),
+                                            &post.date_display,
+                                        );
+                                    }
+                                });
+                            }
+                        }
+
+                        ui.add_space(60.0);
+                    });
+                });
+
+                if let Some(index) = open_post {
+                    self.blog.selected = Some(index);
+                    self.current_page = Page::BlogPost(self.blog.posts[index].slug.clone());
+                    self.push_route_to_browser();
+                } else if back_to_list {
+                    self.blog.selected = None;
+                    self.current_page = Page::Blog;
+                    self.push_route_to_browser();
+                } else if let Some(tag) = open_tag {
+                    if self.navigate_to(Page::BlogTag(tag)) {
+                        self.push_route_to_browser();
+                    }
+                } else if let Some(anchor) = open_anchor {
+                    self.go_to_anchor(anchor);
+                }
+            } else {
+                self.blog.selected = None;
+            }
+        } else {
+            match self.current_page.clone() {
+                Page::BlogTags => self.show_blog_tag_index(ui),
+                Page::BlogSearch(query) => self.show_blog_search(ui, &query),
+                Page::BlogStats => self.show_blog_stats(ui),
+                Page::BlogTag(tag) => self.show_blog_list(ui, Some(&tag)),
+                _ => self.show_blog_list(ui, None),
+            }
+        }
+    }
+
+    /// The public post list, optionally filtered down to posts carrying `tag`.
+    fn show_blog_list(&mut self, ui: &mut egui::Ui, tag: Option<&str>) {
+        ui.add_space(24.0); // Top margin for blog list
+
+        let screen_width = ui.available_width();
+        let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+        let show_dates = screen_width >= 520.0;
+
+        let all_tags: Vec<String> = self
+            .blog_tag_counts()
+            .into_iter()
+            .map(|(tag, _)| tag)
+            .collect();
+        let mut navigate = None;
+
+        ui.horizontal(|ui| {
+            ui.add_space(left_margin); // Responsive left margin
+
+            ui.vertical(|ui| {
+                let content_width = ui.available_width() - right_margin;
+                ui.set_max_width(content_width);
+
+                if tag.is_some() && ui.button("< Back to Blog List").clicked() {
+                    navigate = Some(Page::Blog);
+                }
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
+                match tag {
+                    Some(tag) => ui.label(format!("#{}", tag)),
+                    None => ui.label("Blog"),
+                };
+
+                if tag.is_none() {
+                    ui.add_space(8.0);
+                    if let Some(page) = self.blog_search_box(ui) {
+                        navigate = Some(page);
+                    }
+                }
+
+                if !all_tags.is_empty() {
+                    ui.add_space(8.0);
+                    if let Some(clicked) = Self::tag_chips(ui, &all_tags, tag) {
+                        navigate = Some(if tag == Some(clicked.as_str()) {
+                            Page::Blog
+                        } else {
+                            Page::BlogTag(clicked)
+                        });
+                    }
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                    ui.horizontal(|ui| {
+                        if ui.link("All tags").clicked() {
+                            navigate = Some(Page::BlogTags);
+                        }
+                        if tag.is_none() && ui.link("Stats").clicked() {
+                            navigate = Some(Page::BlogStats);
+                        }
+                    });
+                }
+
+                ui.add_space(40.0);
+
+                let indices: Vec<usize> = (0..self.blog.posts.len())
+                    .filter(|&index| {
+                        tag.map_or(true, |tag| {
+                            self.blog.posts[index].meta.tags.iter().any(|t| t == tag)
+                        })
+                    })
+                    .collect();
+
+                if indices.is_empty() {
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                    ui.label("No posts here yet.");
+                }
+
+                for index in indices {
+                    let blog_post = &self.blog.posts[index];
+                    let clicked = Self::post_row(ui, blog_post, show_dates, true, tag, |ui| {
+                        if let Some(summary) = &blog_post.meta.summary {
+                            ui.label(summary);
+                        }
+                    });
+                    match clicked {
+                        Some(PostRowClick::Title) => {
+                            self.blog.selected = Some(index);
+                            self.current_page = Page::BlogPost(blog_post.slug.clone());
+                            self.push_route_to_browser();
+                        }
+                        Some(PostRowClick::Tag(clicked)) => navigate = Some(Page::BlogTag(clicked)),
+                        None => {}
+                    }
+                }
+
+                ui.add_space(40.0);
+            });
+        });
+
+        if let Some(page) = navigate {
+            if self.navigate_to(page) {
+                self.push_route_to_browser();
+            }
+        }
+    }
+
+    fn show_blog_search(&mut self, ui: &mut egui::Ui, query: &str) {
+        ui.add_space(24.0);
+
+        let screen_width = ui.available_width();
+        let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+        let show_dates = screen_width >= 520.0;
+
+        let hits = self.blog.search_index.search(query);
+        let mut navigate = None;
+        let mut open_post = None;
+
+        ui.horizontal(|ui| {
+            ui.add_space(left_margin);
+
+            ui.vertical(|ui| {
+                let content_width = ui.available_width() - right_margin;
+                ui.set_max_width(content_width);
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
+                ui.label("Blog");
+
+                ui.add_space(8.0);
+                if let Some(page) = self.blog_search_box(ui) {
+                    navigate = Some(page);
+                }
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                ui.colored_label(
+                    egui::Color32::from_rgb(120, 120, 120),
+                    match hits.len() {
+                        1 => format!("1 result for \"{}\"", query.trim()),
+                        n => format!("{} results for \"{}\"", n, query.trim()),
+                    },
+                );
+
+                ui.add_space(32.0);
+
+                for hit in &hits {
+                    let Some(blog_post) = self.blog.posts.get(hit.doc) else {
+                        continue;
+                    };
+                    let segments = search::snippet(&blog_post.content, query, 200);
+                    let clicked = Self::post_row(ui, blog_post, show_dates, false, None, |ui| {
+                        ui.label(Self::highlighted_snippet(ui, &segments));
+                    });
+                    if clicked.is_some() {
+                        open_post = Some(hit.doc);
+                    }
+                }
+
+                ui.add_space(40.0);
+            });
+        });
+
+        if let Some(index) = open_post {
+            self.blog.selected = Some(index);
+            self.current_page = Page::BlogPost(self.blog.posts[index].slug.clone());
+            self.push_route_to_browser();
+        } else if let Some(page) = navigate {
+            // Typing refines the current search rather than adding history entries.
+            if self.navigate_to(page) {
+                self.replace_route_in_browser();
+            }
+        }
+    }
+
+    /// The search box shown on the blog list and search results. Returns the
+    /// page to switch to when the query changes.
+    fn blog_search_box(&mut self, ui: &mut egui::Ui) -> Option<Page> {
+        ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+        // A fixed id keeps keyboard focus when the list turns into the results page.
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut self.blog.search_query)
+                .id(egui::Id::new("blog_search"))
+                .hint_text("Search posts…")
+                .desired_width(ui.available_width().min(360.0)),
+        );
+
+        if !response.changed() {
+            return None;
+        }
+        if self.blog.search_query.trim().is_empty() {
+            Some(Page::Blog)
+        } else {
+            Some(Page::BlogSearch(self.blog.search_query.clone()))
+        }
+    }
+
+    fn highlighted_snippet(ui: &egui::Ui, segments: &[(String, bool)]) -> egui::text::LayoutJob {
+        let font_id = egui::TextStyle::Body.resolve(ui.style());
+        let color = ui.visuals().text_color();
+        let highlight = ui.visuals().hyperlink_color.linear_multiply(0.3);
+
+        let mut job = egui::text::LayoutJob::default();
+        for (text, is_match) in segments {
+            let format = egui::TextFormat {
+                font_id: font_id.clone(),
+                color,
+                background: if *is_match {
+                    highlight
+                } else {
+                    egui::Color32::TRANSPARENT
+                },
+                ..Default::default()
+            };
+            job.append(text, 0.0, format);
+        }
+        job
+    }
+
+    fn show_blog_tag_index(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(24.0);
+
+        let screen_width = ui.available_width();
+        let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+        let mut navigate = None;
+
+        ui.horizontal(|ui| {
+            ui.add_space(left_margin);
+
+            ui.vertical(|ui| {
+                let content_width = ui.available_width() - right_margin;
+                ui.set_max_width(content_width);
+
+                if ui.button("< Back to Blog List").clicked() {
+                    navigate = Some(Page::Blog);
+                }
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
+                ui.label("Tags");
+
+                ui.add_space(32.0);
+
+                for (tag, count) in self.blog_tag_counts() {
+                    ui.horizontal(|ui| {
+                        ui.style_mut().override_text_style =
+                            Some(egui::TextStyle::Name("Heading2".into()));
+                        if ui.link(format!("#{}", tag)).clicked() {
+                            navigate = Some(Page::BlogTag(tag.clone()));
+                        }
+                        ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                        ui.colored_label(
+                            egui::Color32::from_rgb(120, 120, 120),
+                            match count {
+                                1 => "1 post".to_string(),
+                                n => format!("{} posts", n),
+                            },
+                        );
+                    });
+                }
+
+                ui.add_space(40.0);
+            });
+        });
+
+        if let Some(page) = navigate {
+            if self.navigate_to(page) {
+                self.push_route_to_browser();
+            }
+        }
+    }
+
+    /// Words and posts published per year and per month.
+    fn show_blog_stats(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(24.0);
+
+        let screen_width = ui.available_width();
+        let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+        let overall = stats::overall(&self.blog.posts);
+        let by_year = stats::by_year(&self.blog.posts);
+        let by_month = stats::by_month(&self.blog.posts);
+        let mut navigate = None;
+
+        ui.horizontal(|ui| {
+            ui.add_space(left_margin);
+
+            ui.vertical(|ui| {
+                let content_width = ui.available_width() - right_margin;
+                ui.set_max_width(content_width);
+
+                if ui.button("< Back to Blog List").clicked() {
+                    navigate = Some(Page::Blog);
+                }
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
+                ui.label("Stats");
+
+                ui.add_space(8.0);
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                ui.colored_label(
+                    egui::Color32::from_rgb(120, 120, 120),
+                    format!(
+                        "{} in total: {} words, about {} of reading.",
+                        match overall.posts {
+                            1 => "1 post".to_string(),
+                            n => format!("{} posts", n),
+                        },
+                        overall.words,
+                        Self::minutes_display(overall.reading_minutes),
+                    ),
+                );
+
+                ui.add_space(32.0);
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading2".into()));
+                ui.label("By year");
+                ui.add_space(8.0);
+                Self::stats_grid(
+                    ui,
+                    "blog_stats_by_year",
+                    by_year
+                        .iter()
+                        .map(|(year, totals)| (year.to_string(), totals)),
+                );
+
+                ui.add_space(32.0);
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading2".into()));
+                ui.label("By month");
+                ui.add_space(8.0);
+                Self::stats_grid(
+                    ui,
+                    "blog_stats_by_month",
+                    by_month.iter().map(|((year, month), totals)| {
+                        let label = chrono::NaiveDate::from_ymd_opt(*year, *month, 1)
+                            .map(|first| first.format("%B %Y").to_string())
+                            .unwrap_or_else(|| format!("{}-{:02}", year, month));
+                        (label, totals)
+                    }),
+                );
+
+                ui.add_space(40.0);
+            });
+        });
+
+        if let Some(page) = navigate {
+            if self.navigate_to(page) {
+                self.push_route_to_browser();
+            }
+        }
+    }
+
+    /// One row of totals per period, labelled with the period.
+    fn stats_grid<'a>(
+        ui: &mut egui::Ui,
+        id: &str,
+        rows: impl Iterator<Item = (String, &'a Totals)>,
+    ) {
+        ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+        egui::Grid::new(id)
+            .num_columns(6)
+            .spacing(egui::vec2(24.0, 6.0))
+            .striped(true)
+            .show(ui, |ui| {
+                for header in ["", "Posts", "Words", "Reading", "Headings", "Links"] {
+                    ui.colored_label(egui::Color32::from_rgb(120, 120, 120), header);
+                }
+                ui.end_row();
+
+                for (label, totals) in rows {
+                    ui.label(label);
+                    ui.label(totals.posts.to_string());
+                    ui.label(totals.words.to_string());
+                    ui.label(Self::minutes_display(totals.reading_minutes));
+                    ui.label(totals.headings.to_string());
+                    ui.label(totals.links.to_string());
+                    ui.end_row();
+                }
+            });
+    }
+
+    /// "45 min" or "3 h 20 min".
+    fn minutes_display(minutes: usize) -> String {
+        match (minutes / 60, minutes % 60) {
+            (0, minutes) => format!("{} min", minutes),
+            (hours, 0) => format!("{} h", hours),
+            (hours, minutes) => format!("{} h {} min", hours, minutes),
+        }
+    }
+
+    /// Links to the previous (older) and next (newer) post, side by side on
+    /// wide screens and stacked on narrow ones. Returns the index of the one
+    /// clicked.
+    fn post_pager(
+        ui: &mut egui::Ui,
+        previous: Option<(usize, &BlogPost)>,
+        next: Option<(usize, &BlogPost)>,
+        side_by_side: bool,
+    ) -> Option<usize> {
+        let mut clicked = None;
+        let mut link = |ui: &mut egui::Ui, label: &str, post: Option<(usize, &BlogPost)>| {
+            let Some((index, post)) = post else {
+                return;
+            };
+            ui.vertical(|ui| {
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                ui.colored_label(egui::Color32::from_rgb(120, 120, 120), label);
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                if ui.link(&post.title).clicked() {
+                    clicked = Some(index);
+                }
+            });
+        };
+
+        if side_by_side {
+            ui.columns(2, |columns| {
+                link(&mut columns[0], "← Previous", previous);
+                columns[1].with_layout(egui::Layout::top_down(egui::Align::Max), |ui| {
+                    link(ui, "Next →", next);
+                });
+            });
+        } else {
+            link(ui, "← Previous", previous);
+            link(ui, "Next →", next);
+        }
+        clicked
+    }
+
+    /// Marks posts with `draft: true`, which only exist in debug and native
+    /// builds.
+    fn draft_badge(ui: &mut egui::Ui) {
+        ui.label(
+            egui::RichText::new("DRAFT")
+                .text_style(egui::TextStyle::Small)
+                .strong()
+                .color(egui::Color32::from_rgb(217, 119, 6)),
+        );
+    }
+
+    /// Render `tags` as a wrapped row of chips and return the tag that was clicked.
+    fn tag_chips(ui: &mut egui::Ui, tags: &[String], selected: Option<&str>) -> Option<String> {
+        if tags.is_empty() {
+            return None;
+        }
+
+        let mut clicked = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing = egui::vec2(6.0, 6.0);
+            for tag in tags {
+                if ui
+                    .add(TagChip::new(tag, selected == Some(tag.as_str())))
+                    .clicked()
+                {
+                    clicked = Some(tag.clone());
+                }
+            }
+        });
+        clicked
+    }
+
+    /// One post in a list: its title (with a draft badge), date and reading
+    /// time, then `details` such as a summary, and optionally its tags with
+    /// `selected_tag` highlighted. Dates are left out on narrow screens.
+    pub(super) fn post_row(
+        ui: &mut egui::Ui,
+        post: &BlogPost,
+        show_dates: bool,
+        show_tags: bool,
+        selected_tag: Option<&str>,
+        details: impl FnOnce(&mut egui::Ui),
+    ) -> Option<PostRowClick> {
+        let mut clicked = None;
+        egui::Frame::group(ui.style())
+            .stroke(egui::Stroke::NONE)
+            .rounding(egui::Rounding::ZERO)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.vertical(|ui| {
+                    ui.add_space(12.0);
+
+                    ui.horizontal(|ui| {
+                        ui.vertical(|ui| {
+                            ui.style_mut().override_text_style =
+                                Some(egui::TextStyle::Name("Heading2".into()));
+                            if ui.link(&post.title).clicked() {
+                                clicked = Some(PostRowClick::Title);
+                            }
+                            if post.meta.draft {
+                                Self::draft_badge(ui);
+                            }
+                        });
+
+                        if show_dates {
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::TOP), |ui| {
+                                ui.add_space(20.0);
+                                ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                                ui.colored_label(
+                                    egui::Color32::from_rgb(120, 120, 120),
+                                    format!(
+                                        "{} · {}",
+                                        post.date_display,
+                                        post.stats.reading_time()
+                                    ),
+                                );
+                            });
+                        }
+                    });
+
+                    if !show_dates {
+                        ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                        ui.colored_label(
+                            egui::Color32::from_rgb(120, 120, 120),
+                            post.stats.reading_time(),
+                        );
+                    }
+
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                    details(ui);
+
+                    if show_tags {
+                        if let Some(tag) = Self::tag_chips(ui, &post.meta.tags, selected_tag) {
+                            clicked = Some(PostRowClick::Tag(tag));
+                        }
+                    }
+
+                    ui.add_space(16.0);
+                });
+            });
+
+        ui.add_space(16.0);
+        clicked
+    }
+
+    /// Every tag used by a public post with its post count, most used first.
+    fn blog_tag_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for tag in self.blog.posts.iter().flat_map(|post| &post.meta.tags) {
+            match counts.iter_mut().find(|(existing, _)| existing == tag) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((tag.clone(), 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    /// Move scheduled posts whose time has come into the blog, and otherwise
+    /// make sure we repaint when the next one is due.
+    pub(in crate::app) fn publish_scheduled_posts(&mut self, ctx: &egui::Context) {
+        let Some(next) = self
+            .blog
+            .scheduled
+            .iter()
+            .map(|post| post.published_at)
+            .min()
+        else {
+            return;
+        };
+
+        let now = Utc::now();
+        if next > now {
+            ctx.request_repaint_after((next - now).to_std().unwrap_or_default());
+            return;
+        }
+
+        let (due, pending) = split_scheduled(std::mem::take(&mut self.blog.scheduled), now);
+        self.blog.scheduled = pending;
+        self.blog.posts.extend(due);
+        self.blog
+            .posts
+            .sort_by(|a, b| b.published_at.cmp(&a.published_at));
+        self.blog.search_index = build_search_index(&self.blog.posts);
+        self.blog.related = related_posts(&self.blog.posts, &self.blog.search_index);
+        // Indices shifted, so re-resolve the open post from its slug.
+        self.blog.selected = None;
+        self.sync_section_from_route();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posts_are_scheduled_until_their_date_passes() {
+        let posts = BlogPost::load_all(
+            [
+                (
+                    "due.md",
+                    "---\ntitle: a\ndate: \"2025-06-01T12:00:00Z\"\n---\n",
+                ),
+                (
+                    "past.md",
+                    "---\ntitle: b\ndate: \"2025-06-01T11:59:59Z\"\n---\n",
+                ),
+                (
+                    "next.md",
+                    "---\ntitle: c\ndate: \"2025-06-01T12:00:01Z\"\n---\n",
+                ),
+                (
+                    "zoned.md",
+                    "---\ntitle: d\ndate: \"2025-06-01T13:30:00+02:00\"\n---\n",
+                ),
+            ]
+            .into_iter(),
+        );
+        let now = DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let (published, scheduled) = split_scheduled(posts, now);
+        let slugs = |posts: Vec<BlogPost>| -> Vec<String> {
+            posts.into_iter().map(|post| post.slug).collect()
+        };
+        assert_eq!(slugs(published), ["due", "past", "zoned"]);
+        assert_eq!(slugs(scheduled), ["next"]);
+    }
+
+    #[test]
+    fn related_posts_rank_shared_tags_then_wording() {
+        let files: Vec<(String, String)> = [
+            ("a", "[rust]", "typestate pattern borrow checker"),
+            ("b", "[rust]", "gardening tomatoes"),
+            ("c", "[]", "typestate pattern borrow checker lifetimes"),
+            ("d", "[rust]", "cooking pasta"),
+            ("e", "[rust]", "music theory"),
+            ("f", "[]", "zebra crossing"),
+            ("g", "[rust]", "typestate pattern"),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(day, (slug, tags, body))| {
+            let content = format!(
+                "---\ntitle: {}\ndate: 2025-01-{:02}\ntags: {}\n---\n{}\n",
+                slug,
+                day + 1,
+                tags,
+                body
+            );
+            (format!("{}.md", slug), content)
+        })
+        .collect();
+        let posts = BlogPost::load_all(
+            files
+                .iter()
+                .map(|(name, content)| (name.as_str(), content.as_str())),
+        );
+        let slug = |doc: usize| posts[doc].slug.clone();
+        let index = build_search_index(&posts);
+        let related = related_posts(&posts, &index);
+        let related_to = |name: &str| -> Vec<String> {
+            let doc = posts.iter().position(|post| post.slug == name).unwrap();
+            related[doc].iter().map(|&other| slug(other)).collect()
+        };
+
+        // Shared tag and wording first, then tag-only ties in post order
+        // (newest first), capped; the similar but untagged post misses out.
+        assert_eq!(related_to("a"), ["g", "e", "d"]);
+        assert_eq!(related_to("c"), ["a", "g"]);
+        assert!(related_to("f").is_empty());
+        for (doc, others) in related.iter().enumerate() {
+            assert!(!others.contains(&doc));
+            assert!(others.len() <= RELATED_POSTS);
+        }
+    }
+}
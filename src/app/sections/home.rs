@@ -0,0 +1,97 @@
+//! The landing page at `/`: the profile from `site.toml`.
+
+use egui_commonmark::CommonMarkViewer;
+
+use super::Section;
+use crate::app::{MyApp, Page};
+use crate::site;
+
+/// The avatar named in `site.toml`, copied by build.rs; empty if there is none.
+static AVATAR: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/avatar"));
+
+pub(super) struct Home;
+
+impl Section for Home {
+    fn route(&self, segments: &[&str], _query: &str) -> Option<Page> {
+        segments.is_empty().then_some(Page::Home)
+    }
+
+    fn owns(&self, page: &Page) -> bool {
+        matches!(page, Page::Home)
+    }
+
+    fn to_url(&self, _page: &Page) -> String {
+        "/".to_string()
+    }
+
+    fn title(&self, _app: &MyApp, _page: &Page) -> Option<String> {
+        None
+    }
+
+    fn show(&self, app: &mut MyApp, ui: &mut egui::Ui, _page: &Page) {
+        app.show_home(ui);
+    }
+}
+
+impl MyApp {
+    pub(in crate::app) fn show_home(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(24.0); // Top margin
+
+        // Calculate responsive margins
+        let screen_width = ui.available_width();
+        let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+
+        ui.horizontal(|ui| {
+            ui.add_space(left_margin); // Responsive left margin
+
+            ui.vertical(|ui| {
+                // Calculate the content width by subtracting both margins
+                let content_width = ui.available_width() - right_margin;
+                ui.set_max_width(content_width);
+
+                let profile = &site::embedded().profile;
+
+                if !AVATAR.is_empty() {
+                    ui.add(
+                        egui::Image::from_bytes("bytes://avatar", AVATAR)
+                            .fit_to_exact_size(egui::vec2(96.0, 96.0))
+                            .rounding(48.0),
+                    );
+                    ui.add_space(8.0);
+                }
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
+                ui.label(&profile.name);
+
+                if let Some(tagline) = &profile.tagline {
+                    ui.style_mut().override_text_style =
+                        Some(egui::TextStyle::Name("Heading3".into()));
+                    ui.colored_label(egui::Color32::from_rgb(120, 120, 120), tagline);
+                }
+
+                if let Some(bio) = &profile.bio {
+                    ui.add_space(8.0);
+                    ui.scope(|ui| {
+                        ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                        CommonMarkViewer::new("profile_bio").show(
+                            ui,
+                            &mut self.markdown_cache,
+                            bio,
+                        );
+                    });
+                }
+
+                ui.add_space(16.0); // Design system spacing
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading2".into()));
+                for link in &profile.links {
+                    let label = match link.icon {
+                        Some(icon) => format!("{} {}", icon.glyph(), link.label),
+                        None => link.label.clone(),
+                    };
+                    ui.hyperlink_to(label, &link.url);
+                }
+            });
+        });
+    }
+}
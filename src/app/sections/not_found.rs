@@ -0,0 +1,231 @@
+//! The page shown for paths nothing else claims, with suggestions for
+//! what the visitor may have meant.
+
+use super::Section;
+use crate::app::{MarkdownPage, MyApp, Page};
+use crate::search;
+
+/// Paths no other section claims, and post or project slugs that turn out
+/// not to exist (see [`Section::sync`]).
+pub(super) struct NotFound;
+
+impl Section for NotFound {
+    /// Never claims a path itself: [`route`] falls back to it.
+    fn route(&self, _segments: &[&str], _query: &str) -> Option<Page> {
+        None
+    }
+
+    fn owns(&self, page: &Page) -> bool {
+        matches!(page, Page::NotFound(_))
+    }
+
+    /// The path as requested, query included.
+    fn to_url(&self, page: &Page) -> String {
+        match page {
+            Page::NotFound(path) => path.clone(),
+            _ => "/".to_string(),
+        }
+    }
+
+    fn keeps_query_param(&self, _page: &Page, _key: &str) -> bool {
+        true
+    }
+
+    fn title(&self, _app: &MyApp, _page: &Page) -> Option<String> {
+        Some("Page not found".to_string())
+    }
+
+    fn show(&self, app: &mut MyApp, ui: &mut egui::Ui, page: &Page) {
+        if let Page::NotFound(path) = page {
+            app.show_not_found(ui, path);
+        }
+    }
+}
+
+impl MyApp {
+    /// What to show for a path with nothing behind it: the path itself, the
+    /// posts, projects and pages with a similar slug, and a way back.
+    fn show_not_found(&mut self, ui: &mut egui::Ui, path: &str) {
+        let mut navigate = None;
+
+        ui.add_space(24.0); // Top margin
+
+        let screen_width = ui.available_width();
+        let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+
+        ui.horizontal(|ui| {
+            ui.add_space(left_margin); // Responsive left margin
+
+            ui.vertical(|ui| {
+                let content_width = ui.available_width() - right_margin;
+                ui.set_max_width(content_width);
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
+                ui.label("Page not found");
+
+                ui.add_space(12.0);
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("There is nothing at");
+                    ui.code(path);
+                    ui.label("— the link may be mistyped or out of date.");
+                });
+
+                let suggestions = self.not_found_suggestions(path);
+                if !suggestions.is_empty() {
+                    ui.add_space(24.0);
+                    ui.style_mut().override_text_style =
+                        Some(egui::TextStyle::Name("Heading3".into()));
+                    ui.label("Did you mean");
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                    for (label, page) in suggestions {
+                        ui.horizontal(|ui| {
+                            if ui.link(label).clicked() {
+                                navigate = Some(page.clone());
+                            }
+                            ui.colored_label(egui::Color32::from_rgb(120, 120, 120), page.to_url());
+                        });
+                    }
+                }
+
+                ui.add_space(24.0);
+                ui.horizontal_wrapped(|ui| {
+                    ui.spacing_mut().item_spacing.x = 16.0;
+                    if ui.link("Home").clicked() {
+                        navigate = Some(Page::Home);
+                    }
+                    if ui.link("All posts").clicked() {
+                        navigate = Some(Page::Blog);
+                    }
+                    if ui.link("All projects").clicked() {
+                        navigate = Some(Page::Projects);
+                    }
+                });
+
+                ui.add_space(60.0);
+            });
+        });
+
+        if let Some(page) = navigate {
+            if self.navigate_to(page) {
+                self.sync_section_from_route();
+                self.push_route_to_browser();
+            }
+        }
+    }
+
+    /// Public posts, projects and pages whose slug is within a few typos of
+    /// the last segment of `path`, closest first.
+    fn not_found_suggestions(&self, path: &str) -> Vec<(String, Page)> {
+        const MAX_SUGGESTIONS: usize = 3;
+
+        let path = path.split(['?', '#']).next().unwrap_or_default();
+        let Some(requested) = path
+            .rsplit('/')
+            .find(|segment| !segment.is_empty())
+            .map(str::to_lowercase)
+        else {
+            return Vec::new();
+        };
+        // Roughly one typo per three characters, but always allow two.
+        let max_distance = (requested.chars().count() / 3).max(2);
+
+        let posts = self
+            .blog
+            .posts
+            .iter()
+            .map(|post| (&post.slug, &post.title, Page::BlogPost(post.slug.clone())));
+        let projects = self.projects.all.iter().map(|project| {
+            (
+                &project.slug,
+                &project.meta.name,
+                Page::Project(project.slug.clone()),
+            )
+        });
+        let pages = MarkdownPage::embedded().iter().map(|page| {
+            (
+                &page.slug,
+                &page.meta.title,
+                Page::Section(page.slug.clone()),
+            )
+        });
+
+        let mut matches: Vec<(usize, String, Page)> = posts
+            .chain(projects)
+            .chain(pages)
+            .filter_map(|(slug, label, page)| {
+                let distance = search::edit_distance(&requested, &slug.to_lowercase());
+                (distance <= max_distance).then(|| (distance, label.clone(), page))
+            })
+            .collect();
+        matches.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+        matches
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, label, page)| (label, page))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    use crate::app::{BlogPost, BlogState};
+
+    #[test]
+    fn not_found_suggests_the_closest_slugs() {
+        let files = [
+            (
+                "hello-world.md",
+                "---\ntitle: Hello World\ndate: 2025-01-01\n---\n",
+            ),
+            (
+                "help-world.md",
+                "---\ntitle: A Help World\ndate: 2025-01-02\n---\n",
+            ),
+            (
+                "hello-worlds.md",
+                "---\ntitle: Hello Worlds\ndate: 2025-01-03\n---\n",
+            ),
+            (
+                "hello-world-2.md",
+                "---\ntitle: Hello Again\ndate: 2025-01-04\n---\n",
+            ),
+            (
+                "rust-tips.md",
+                "---\ntitle: Rust Tips\ndate: 2025-01-05\n---\n",
+            ),
+        ];
+        let app = MyApp {
+            blog: BlogState::new(BlogPost::load_all(files.into_iter()), Utc::now()),
+            ..MyApp::default()
+        };
+        let labels = |path: &str| -> Vec<String> {
+            app.not_found_suggestions(path)
+                .into_iter()
+                .map(|(label, _)| label)
+                .collect()
+        };
+
+        // One typo away, ties by title, then two. `hello-world-2` is three
+        // away, which a ten-letter slug still allows, but past the cap;
+        // `rust-tips` is too far off.
+        assert_eq!(
+            labels("/blog/helo-world"),
+            ["A Help World", "Hello World", "Hello Worlds"]
+        );
+        assert!(labels("/blog/zzzz-qqqqq").is_empty());
+
+        let about = MarkdownPage::embedded()
+            .iter()
+            .find(|page| page.slug == "about")
+            .unwrap();
+        assert_eq!(
+            app.not_found_suggestions("/abuot?ref=x#top"),
+            [(about.meta.title.clone(), Page::Section("about".to_string()))]
+        );
+    }
+}
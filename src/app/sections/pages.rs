@@ -0,0 +1,127 @@
+//! The standalone Markdown pages from `pages/`.
+
+use super::Section;
+use crate::app::{MarkdownPage, MyApp, Page};
+use crate::headings::Outline;
+use crate::nav::{NavItem, NavTarget};
+use crate::site::NavVisibility;
+
+/// The standalone pages in `pages/`, each at `/{slug}`.
+pub(super) struct MarkdownPages;
+
+impl Section for MarkdownPages {
+    fn route(&self, segments: &[&str], _query: &str) -> Option<Page> {
+        match segments {
+            [slug] => MarkdownPage::find(slug).map(|page| Page::Section(page.slug.clone())),
+            _ => None,
+        }
+    }
+
+    fn owns(&self, page: &Page) -> bool {
+        Self::page(page).is_some()
+    }
+
+    fn to_url(&self, page: &Page) -> String {
+        match page {
+            Page::Section(path) => format!("/{}", path),
+            _ => "/".to_string(),
+        }
+    }
+
+    /// A nav entry for `/docs` also stands for `/docs/...`.
+    fn is_within(&self, page: &Page, root: &Page) -> bool {
+        match (page, root) {
+            (Page::Section(path), Page::Section(prefix)) => {
+                path == prefix || path.starts_with(&format!("{}/", prefix))
+            }
+            _ => false,
+        }
+    }
+
+    fn title(&self, _app: &MyApp, page: &Page) -> Option<String> {
+        Self::page(page).map(|page| page.meta.title.clone())
+    }
+
+    fn outline<'a>(&self, _app: &'a MyApp, page: &Page) -> Option<&'a Outline> {
+        Self::page(page).map(|page| &page.outline)
+    }
+
+    /// Pages with `nav: true`, placed by their `nav_order`.
+    fn nav_items(&self) -> Vec<NavItem> {
+        MarkdownPage::embedded()
+            .iter()
+            .filter(|page| page.meta.nav)
+            .map(|page| NavItem {
+                label: page.meta.title.clone(),
+                target: NavTarget::Page(Page::Section(page.slug.clone())),
+                visibility: NavVisibility::Always,
+                order: page.meta.nav_order,
+            })
+            .collect()
+    }
+
+    fn show(&self, app: &mut MyApp, ui: &mut egui::Ui, page: &Page) {
+        if let Some(page) = Self::page(page) {
+            app.show_markdown_page(ui, page);
+        }
+    }
+}
+
+impl MarkdownPages {
+    fn page(page: &Page) -> Option<&'static MarkdownPage> {
+        match page {
+            Page::Section(slug) => MarkdownPage::find(slug),
+            _ => None,
+        }
+    }
+}
+
+impl MyApp {
+    /// A page from `pages/`: laid out like a post, without dates or tags.
+    fn show_markdown_page(&mut self, ui: &mut egui::Ui, page: &MarkdownPage) {
+        let scroll_to = self.take_scroll_anchor();
+        let mut open_anchor = None;
+
+        ui.add_space(24.0); // Top margin
+
+        let screen_width = ui.available_width();
+        let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+
+        ui.horizontal(|ui| {
+            ui.add_space(left_margin); // Responsive left margin
+
+            ui.vertical(|ui| {
+                let content_width = ui.available_width() - right_margin;
+                ui.set_max_width(content_width);
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
+                ui.label(&page.meta.title);
+
+                open_anchor = Self::contents_dropdown(ui, &page.outline.headings);
+
+                ui.add_space(40.0);
+
+                ui.spacing_mut().item_spacing.y = 20.0;
+                ui.spacing_mut().indent = 24.0;
+
+                let viewer_id = format!("page_{}", page.slug.as_str());
+                ui.scope(|ui| {
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                    Self::show_markdown_sections(
+                        ui,
+                        &mut self.markdown_cache,
+                        &viewer_id,
+                        &page.outline,
+                        scroll_to.as_deref(),
+                    );
+                });
+
+                ui.add_space(60.0);
+            });
+        });
+
+        if let Some(anchor) = open_anchor {
+            self.go_to_anchor(anchor);
+        }
+    }
+}
@@ -0,0 +1,638 @@
+//! The password-protected blog at `/private`: the prompt, the posts it
+//! unlocks, the remembered session and the back-off after wrong passwords.
+
+use chrono::{DateTime, Utc};
+
+use super::Section;
+use crate::app::{BlogPost, MyApp, Page};
+use crate::headings::Outline;
+use crate::vault;
+
+pub(super) struct PrivateBlog;
+
+impl Section for PrivateBlog {
+    fn route(&self, segments: &[&str], _query: &str) -> Option<Page> {
+        match segments {
+            ["private"] => Some(Page::PrivateBlog),
+            ["private", slug] => Some(Page::PrivateBlogPost(slug.to_string())),
+            _ => None,
+        }
+    }
+
+    fn owns(&self, page: &Page) -> bool {
+        matches!(page, Page::PrivateBlog | Page::PrivateBlogPost(_))
+    }
+
+    fn to_url(&self, page: &Page) -> String {
+        match page {
+            Page::PrivateBlogPost(slug) => format!("/private/{}", slug),
+            _ => "/private".to_string(),
+        }
+    }
+
+    fn is_within(&self, page: &Page, root: &Page) -> bool {
+        *root == Page::PrivateBlog || page == root
+    }
+
+    /// Selects the post a `/private/{slug}` names. Until the password is
+    /// entered there is nothing to resolve the slug against, so the route is
+    /// kept and the prompt shown; after that, an unknown slug goes back to
+    /// the list.
+    fn sync(&self, app: &mut MyApp, page: &Page) {
+        app.private.selected = None;
+        let Page::PrivateBlogPost(slug) = page else {
+            return;
+        };
+        if !app.private.is_authenticated {
+            return;
+        }
+        match app.private.posts.iter().position(|post| &post.slug == slug) {
+            Some(index) => app.private.selected = Some(index),
+            None => {
+                app.current_page = Page::PrivateBlog;
+                app.replace_route_in_browser();
+            }
+        }
+    }
+
+    /// Never the post title: tab titles end up in history and window lists.
+    fn title(&self, _app: &MyApp, _page: &Page) -> Option<String> {
+        Some("Private".to_string())
+    }
+
+    fn outline<'a>(&self, app: &'a MyApp, page: &Page) -> Option<&'a Outline> {
+        match page {
+            Page::PrivateBlogPost(_) => app
+                .private
+                .selected
+                .and_then(|index| app.private.posts.get(index))
+                .map(|post| &post.outline),
+            _ => None,
+        }
+    }
+
+    fn show(&self, app: &mut MyApp, ui: &mut egui::Ui, _page: &Page) {
+        app.show_private_blog(ui);
+    }
+}
+
+/// What the private blog keeps between frames. None of it is written under
+/// `eframe::APP_KEY`: the session and throttle have storage keys of their
+/// own, and the rest must not outlive the tab.
+#[derive(Default)]
+pub(in crate::app) struct PrivateBlogState {
+    /// Decrypted once the password is entered.
+    posts: Vec<BlogPost>,
+    selected: Option<usize>,
+    pub(in crate::app) is_authenticated: bool,
+    password_input: String,
+    unlock_error: Option<String>,
+    /// Access groups the entered password opened.
+    unlocked_groups: Vec<String>,
+    remember_device: bool,
+    /// Persisted separately under `PRIVATE_SESSION_KEY`.
+    session: Option<PrivateSession>,
+    /// Persisted separately under `UNLOCK_THROTTLE_KEY`.
+    throttle: UnlockThrottle,
+    /// The session or throttle changed and must be written out now.
+    pub(in crate::app) storage_dirty: bool,
+}
+
+/// `private_blog_posts/`, encrypted by build.rs per access group (see
+/// `src/vault.rs`). Only ciphertext is embedded.
+mod sealed_private_posts {
+    include!(concat!(env!("OUT_DIR"), "/private_posts.rs"));
+}
+
+/// Storage key of the remembered private blog session, kept apart from
+/// `eframe::APP_KEY` so it can be written (and cleared) as soon as it changes.
+const PRIVATE_SESSION_KEY: &str = "private_session";
+
+/// How long "remember this device" keeps the private blog unlocked.
+const REMEMBER_DEVICE_DAYS: i64 = 14;
+
+/// A remembered private blog unlock: the password-derived key (never the
+/// password itself) and when the page stops accepting it. The expiry is only
+/// checked here; the key keeps decrypting the posts for as long as the
+/// password stays the same.
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+struct PrivateSession {
+    key: vault::SecretKey,
+    /// Unix timestamp in seconds.
+    expires_at: i64,
+}
+
+impl PrivateSession {
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now.timestamp() >= self.expires_at
+    }
+}
+
+/// Storage key of the failed-unlock counter, so reloading the page does not
+/// reset the back-off.
+const UNLOCK_THROTTLE_KEY: &str = "private_unlock_throttle";
+
+/// Wrong passwords allowed before any waiting is imposed.
+const FREE_UNLOCK_ATTEMPTS: u32 = 3;
+
+/// The first wait; each further failure doubles it.
+const UNLOCK_BACKOFF_BASE_SECS: i64 = 5;
+
+/// The back-off stops growing here, which amounts to a one hour lockout.
+const UNLOCK_BACKOFF_MAX_SECS: i64 = 60 * 60;
+
+/// Failed password attempts and when the next one is allowed.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+struct UnlockThrottle {
+    failed_attempts: u32,
+    /// Unix timestamp in seconds; no waiting if it is in the past.
+    retry_at: i64,
+}
+
+impl UnlockThrottle {
+    /// Seconds until another attempt is allowed, if any.
+    fn wait_secs(&self, now: DateTime<Utc>) -> Option<i64> {
+        let wait = self.retry_at - now.timestamp();
+        (wait > 0).then_some(wait)
+    }
+
+    fn record_failure(&mut self, now: DateTime<Utc>) {
+        self.failed_attempts += 1;
+        let Some(excess) = self.failed_attempts.checked_sub(FREE_UNLOCK_ATTEMPTS) else {
+            return;
+        };
+        let backoff = UNLOCK_BACKOFF_BASE_SECS
+            .saturating_mul(1 << excess.min(20))
+            .min(UNLOCK_BACKOFF_MAX_SECS);
+        self.retry_at = now.timestamp() + backoff;
+    }
+
+    /// Forget the failures after a correct password; `true` if there were any.
+    fn record_success(&mut self) -> bool {
+        let had_failures = self.failed_attempts > 0;
+        *self = Self::default();
+        had_failures
+    }
+}
+
+impl MyApp {
+    fn show_private_blog(&mut self, ui: &mut egui::Ui) {
+        // Check if authenticated
+        if !self.private.is_authenticated {
+            // Show password prompt
+            ui.add_space(24.0);
+
+            let screen_width = ui.available_width();
+            let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+
+            ui.horizontal(|ui| {
+                ui.add_space(left_margin);
+
+                ui.vertical(|ui| {
+                    let content_width = ui.available_width() - right_margin;
+                    ui.set_max_width(content_width);
+
+                    ui.style_mut().override_text_style =
+                        Some(egui::TextStyle::Name("Heading1".into()));
+                    ui.label("Private Blog");
+
+                    ui.add_space(40.0);
+
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                    ui.label("This section is password protected.");
+
+                    ui.add_space(20.0);
+
+                    let wait_secs = self.private.throttle.wait_secs(Utc::now());
+
+                    ui.horizontal(|ui| {
+                        ui.add_enabled_ui(wait_secs.is_none(), |ui| {
+                            ui.label("Password:");
+                            let response = ui.add(
+                                egui::TextEdit::singleline(&mut self.private.password_input)
+                                    .password(true)
+                                    .desired_width(200.0),
+                            );
+
+                            let enter_pressed = response.lost_focus()
+                                && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                            let submit_clicked = ui.button("Submit").clicked();
+                            if (enter_pressed || submit_clicked)
+                                && !self.unlock_private_blog()
+                                && self.private.throttle.wait_secs(Utc::now()).is_none()
+                            {
+                                response.request_focus();
+                            }
+                        });
+                    });
+
+                    ui.checkbox(
+                        &mut self.private.remember_device,
+                        format!("Remember this device for {} days", REMEMBER_DEVICE_DAYS),
+                    );
+
+                    match wait_secs {
+                        Some(wait) => {
+                            // Count down once a second.
+                            ui.ctx()
+                                .request_repaint_after(std::time::Duration::from_secs(1));
+                            ui.colored_label(
+                                ui.visuals().error_fg_color,
+                                format!(
+                                    "Wrong password, try again in {}.",
+                                    Self::format_wait(wait)
+                                ),
+                            );
+                        }
+                        None => {
+                            if let Some(error) = &self.private.unlock_error {
+                                ui.colored_label(ui.visuals().error_fg_color, error);
+                            }
+                        }
+                    }
+
+                    ui.add_space(40.0);
+                });
+            });
+
+            return;
+        }
+
+        // Authenticated - show private blog content
+        if let Some(blog_index) = self.private.selected {
+            let scroll_to = self.take_scroll_anchor();
+            if let Some(blog_post) = self.private.posts.get(blog_index) {
+                let mut back_to_list = false;
+                let mut lock = false;
+                let mut open_anchor = None;
+
+                ui.add_space(16.0);
+
+                let screen_width = ui.available_width();
+                let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+                let show_dates = screen_width >= 520.0;
+
+                ui.horizontal(|ui| {
+                    ui.add_space(left_margin);
+
+                    ui.vertical(|ui| {
+                        let content_width = ui.available_width() - right_margin;
+                        ui.set_max_width(content_width);
+
+                        ui.horizontal(|ui| {
+                            if ui.button("< Back to Private Blog List").clicked() {
+                                back_to_list = true;
+                            }
+                            ui.with_layout(
+                                egui::Layout::right_to_left(egui::Align::Center),
+                                |ui| {
+                                    lock = ui.button("Lock").clicked();
+                                },
+                            );
+                        });
+
+                        ui.add_space(32.0);
+
+                        ui.style_mut().override_text_style =
+                            Some(egui::TextStyle::Name("Heading1".into()));
+                        ui.label(&blog_post.title);
+
+                        ui.add_space(12.0);
+
+                        if show_dates {
+                            ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                            ui.colored_label(
+                                egui::Color32::from_rgb(120, 120, 120),
+                                format!(
+                                    "{} · {}",
+                                    blog_post.date_display,
+                                    blog_post.stats.reading_time()
+                                ),
+                            );
+                        }
+
+                        open_anchor = Self::contents_dropdown(ui, &blog_post.outline.headings);
+
+                        ui.add_space(40.0);
+
+                        ui.spacing_mut().item_spacing.y = 20.0;
+                        ui.spacing_mut().indent = 24.0;
+
+                        let viewer_id = format!("private_blog_{}", blog_post.slug.as_str());
+                        ui.scope(|ui| {
+                            ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                            Self::show_markdown_sections(
+                                ui,
+                                &mut self.markdown_cache,
+                                &viewer_id,
+                                &blog_post.outline,
+                                scroll_to.as_deref(),
+                            );
+                        });
+
+                        ui.add_space(60.0);
+                    });
+                });
+
+                if lock {
+                    self.lock_private_blog();
+                } else if back_to_list {
+                    self.private.selected = None;
+                    self.current_page = Page::PrivateBlog;
+                    self.push_route_to_browser();
+                } else if let Some(anchor) = open_anchor {
+                    self.go_to_anchor(anchor);
+                }
+            } else {
+                self.private.selected = None;
+            }
+        } else {
+            // Show private blog list
+            let mut lock = false;
+            ui.add_space(24.0);
+
+            let screen_width = ui.available_width();
+            let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+            let show_dates = screen_width >= 520.0;
+
+            ui.horizontal(|ui| {
+                ui.add_space(left_margin);
+
+                ui.vertical(|ui| {
+                    let content_width = ui.available_width() - right_margin;
+                    ui.set_max_width(content_width);
+
+                    ui.style_mut().override_text_style =
+                        Some(egui::TextStyle::Name("Heading1".into()));
+                    ui.horizontal(|ui| {
+                        ui.label("Private Blog");
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            ui.style_mut().override_text_style = Some(egui::TextStyle::Button);
+                            lock = ui.button("Lock").clicked();
+                        });
+                    });
+
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                    let mut status =
+                        format!("Unlocked: {}", self.private.unlocked_groups.join(", "));
+                    if let Some(expires_at) = self
+                        .private
+                        .session
+                        .as_ref()
+                        .and_then(|session| DateTime::from_timestamp(session.expires_at, 0))
+                    {
+                        status += &format!(
+                            " · remembered on this device until {}",
+                            expires_at.format("%B %-d, %Y")
+                        );
+                    }
+                    ui.colored_label(egui::Color32::from_rgb(120, 120, 120), status);
+
+                    ui.add_space(40.0);
+
+                    for index in 0..self.private.posts.len() {
+                        let blog_post = &self.private.posts[index];
+                        // Tags of private posts don't lead anywhere, so they aren't shown.
+                        if Self::post_row(ui, blog_post, show_dates, false, None, |_| {}).is_some()
+                        {
+                            self.private.selected = Some(index);
+                            self.current_page = Page::PrivateBlogPost(blog_post.slug.clone());
+                            self.push_route_to_browser();
+                        }
+                    }
+
+                    ui.add_space(40.0);
+                });
+            });
+
+            if lock {
+                self.lock_private_blog();
+            }
+        }
+    }
+
+    /// Derive the key from `password_input` and decrypt the private posts of
+    /// every access group it opens. Returns whether it opened any; on failure
+    /// the reason is left in `private_unlock_error`.
+    fn unlock_private_blog(&mut self) -> bool {
+        if sealed_private_posts::GROUPS.is_empty() {
+            self.private.unlock_error =
+                Some("This build does not include any private posts.".to_string());
+            return false;
+        }
+
+        let now = Utc::now();
+        if self.private.throttle.wait_secs(now).is_some() {
+            return false;
+        }
+
+        let key = vault::derive_key(
+            &self.private.password_input,
+            &sealed_private_posts::SALT,
+            sealed_private_posts::ITERATIONS,
+        );
+        if !self.unlock_private_blog_with_key(&key) {
+            self.private.throttle.record_failure(now);
+            self.private.storage_dirty = true;
+            self.private.unlock_error = Some("Wrong password, please try again.".to_string());
+            return false;
+        }
+        if self.private.throttle.record_success() {
+            self.private.storage_dirty = true;
+        }
+
+        if self.private.remember_device {
+            let expires_at = Utc::now() + chrono::Duration::days(REMEMBER_DEVICE_DAYS);
+            self.private.session = Some(PrivateSession {
+                key,
+                expires_at: expires_at.timestamp(),
+            });
+            self.private.storage_dirty = true;
+        }
+        self.private.password_input.clear();
+        self.private.unlock_error = None;
+        true
+    }
+
+    /// "45s", or "12m 5s" for longer waits.
+    fn format_wait(secs: i64) -> String {
+        match (secs / 60, secs % 60) {
+            (0, secs) => format!("{}s", secs),
+            (mins, 0) => format!("{}m", mins),
+            (mins, secs) => format!("{}m {}s", mins, secs),
+        }
+    }
+
+    /// Decrypt the posts of every access group `key` opens.
+    fn unlock_private_blog_with_key(&mut self, key: &vault::SecretKey) -> bool {
+        let mut files = Vec::new();
+        let mut unlocked_groups = Vec::new();
+        for group in sealed_private_posts::GROUPS {
+            let Some(content_key) = vault::unwrap_key(key, group) else {
+                continue;
+            };
+            files.extend(
+                group
+                    .posts
+                    .iter()
+                    .filter_map(|sealed| vault::open(&content_key, sealed)),
+            );
+            unlocked_groups.push(group.name.to_string());
+        }
+
+        if unlocked_groups.is_empty() {
+            return false;
+        }
+
+        self.private.posts = BlogPost::load_all(
+            files
+                .iter()
+                .map(|(name, content)| (name.as_str(), content.as_str())),
+        );
+        self.private.unlocked_groups = unlocked_groups;
+        self.private.is_authenticated = true;
+        self.sync_section_from_route();
+        true
+    }
+
+    /// Forget the decrypted posts and any remembered session, returning to
+    /// the password prompt.
+    fn lock_private_blog(&mut self) {
+        self.private.posts.clear();
+        self.private.selected = None;
+        self.private.unlocked_groups.clear();
+        self.private.is_authenticated = false;
+        if self.private.session.take().is_some() {
+            self.private.storage_dirty = true;
+        }
+    }
+
+    /// Pick up the unlock throttle and, if it is still valid for this build,
+    /// the session remembered on this device. An expired or stale session is
+    /// dropped, and cleared from storage at the end of the frame.
+    pub(in crate::app) fn restore_private_blog(
+        &mut self,
+        storage: Option<&dyn eframe::Storage>,
+        now: DateTime<Utc>,
+    ) {
+        self.private.throttle = storage
+            .and_then(|storage| eframe::get_value(storage, UNLOCK_THROTTLE_KEY))
+            .unwrap_or_default();
+        let session = storage
+            .and_then(|storage| {
+                eframe::get_value::<Option<PrivateSession>>(storage, PRIVATE_SESSION_KEY)
+            })
+            .flatten();
+        let Some(session) = session else {
+            return;
+        };
+
+        // A rebuild with new passwords or salt also invalidates the session.
+        if session.is_expired(now) || !self.unlock_private_blog_with_key(&session.key) {
+            self.private.storage_dirty = true;
+            return;
+        }
+        self.private.remember_device = true;
+        self.private.session = Some(session);
+    }
+
+    /// Write the session and throttle under their own storage keys.
+    pub(in crate::app) fn save_private_blog(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, PRIVATE_SESSION_KEY, &self.private.session);
+        eframe::set_value(storage, UNLOCK_THROTTLE_KEY, &self.private.throttle);
+    }
+
+    /// Lock the private blog once the remembered session runs out, and
+    /// otherwise make sure we repaint when it does.
+    pub(in crate::app) fn expire_private_session(&mut self, ctx: &egui::Context) {
+        let Some(session) = &self.private.session else {
+            return;
+        };
+
+        let now = Utc::now();
+        if session.is_expired(now) {
+            self.lock_private_blog();
+        } else {
+            let remaining = session.expires_at - now.timestamp();
+            ctx.request_repaint_after(std::time::Duration::from_secs(remaining as u64));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// Storage kept in memory, as `eframe` would keep it on disk.
+    #[derive(Default)]
+    struct MemoryStorage(HashMap<String, String>);
+
+    impl eframe::Storage for MemoryStorage {
+        fn get_string(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn set_string(&mut self, key: &str, value: String) {
+            self.0.insert(key.to_string(), value);
+        }
+
+        fn flush(&mut self) {}
+    }
+
+    #[test]
+    fn expired_session_is_dropped_and_cleared_on_load() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let mut storage = MemoryStorage::default();
+        let session = PrivateSession {
+            key: vault::derive_key("hunter2", &[7; vault::SALT_LEN], 1),
+            expires_at: now.timestamp(),
+        };
+        assert!(session.is_expired(now));
+        assert!(!session.is_expired(now - chrono::Duration::seconds(1)));
+        eframe::set_value(&mut storage, PRIVATE_SESSION_KEY, &Some(session));
+
+        let mut app = MyApp::default();
+        app.restore_private_blog(Some(&storage), now);
+        assert!(!app.private.is_authenticated);
+        assert!(!app.private.remember_device);
+        assert!(app.private.session.is_none());
+        assert!(app.private.storage_dirty);
+
+        app.save_private_blog(&mut storage);
+        let stored: Option<Option<PrivateSession>> =
+            eframe::get_value(&storage, PRIVATE_SESSION_KEY);
+        assert!(matches!(stored, Some(None)));
+    }
+
+    #[test]
+    fn unlock_throttle_waits_from_the_third_failure_doubles_and_resets() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let mut throttle = UnlockThrottle::default();
+        for _ in 1..FREE_UNLOCK_ATTEMPTS {
+            throttle.record_failure(now);
+            assert_eq!(throttle.wait_secs(now), None);
+        }
+
+        let mut expected = UNLOCK_BACKOFF_BASE_SECS;
+        for attempt in FREE_UNLOCK_ATTEMPTS..FREE_UNLOCK_ATTEMPTS + 20 {
+            throttle.record_failure(now);
+            assert_eq!(
+                throttle.wait_secs(now),
+                Some(expected),
+                "attempt {}",
+                attempt
+            );
+            expected = (expected * 2).min(UNLOCK_BACKOFF_MAX_SECS);
+        }
+        assert_eq!(throttle.wait_secs(now), Some(UNLOCK_BACKOFF_MAX_SECS));
+        let later = now + chrono::Duration::seconds(UNLOCK_BACKOFF_MAX_SECS);
+        assert_eq!(throttle.wait_secs(later), None);
+
+        assert!(throttle.record_success());
+        assert_eq!(throttle.failed_attempts, 0);
+        assert_eq!(throttle.wait_secs(now), None);
+        assert!(!throttle.record_success());
+    }
+}
@@ -0,0 +1,497 @@
+//! The project list at `/projects`, with its filters, and a page per
+//! project.
+
+use egui_commonmark::CommonMarkViewer;
+
+use super::Section;
+use crate::app::{MyApp, Page, Project, Route, TagChip};
+use crate::frontmatter;
+use crate::projects::{ProjectMeta, ProjectSort, ProjectStatus};
+
+pub(super) struct Projects;
+
+impl Section for Projects {
+    fn route(&self, segments: &[&str], _query: &str) -> Option<Page> {
+        match segments {
+            ["projects"] => Some(Page::Projects),
+            ["projects", slug] => Some(Page::Project(slug.to_string())),
+            _ => None,
+        }
+    }
+
+    fn owns(&self, page: &Page) -> bool {
+        matches!(page, Page::Projects | Page::Project(_))
+    }
+
+    fn to_url(&self, page: &Page) -> String {
+        match page {
+            Page::Project(slug) => format!("/projects/{}", slug),
+            _ => "/projects".to_string(),
+        }
+    }
+
+    fn is_within(&self, page: &Page, root: &Page) -> bool {
+        *root == Page::Projects || page == root
+    }
+
+    /// Reads the list's filter from the route's query. A project that
+    /// doesn't exist is a broken link.
+    fn sync(&self, app: &mut MyApp, page: &Page) {
+        match page {
+            Page::Projects => {
+                let filter = ProjectFilter::from_query(&app.current_route().query);
+                if app.projects.filter != filter {
+                    app.projects.filter = filter;
+                }
+            }
+            Page::Project(slug)
+                if !app.projects.all.iter().any(|project| &project.slug == slug) =>
+            {
+                app.current_page = Page::NotFound(page.to_url());
+            }
+            _ => {}
+        }
+    }
+
+    fn title(&self, app: &MyApp, page: &Page) -> Option<String> {
+        let project = match page {
+            Page::Project(slug) => app
+                .projects
+                .all
+                .iter()
+                .find(|project| &project.slug == slug),
+            _ => None,
+        };
+        Some(project.map_or_else(
+            || "Projects".to_string(),
+            |project| project.meta.name.clone(),
+        ))
+    }
+
+    fn show(&self, app: &mut MyApp, ui: &mut egui::Ui, page: &Page) {
+        match page {
+            Page::Project(slug) => app.show_project(ui, slug),
+            Page::Projects => app.show_projects(ui),
+            _ => {}
+        }
+    }
+}
+
+/// The projects, loaded from the embedded `projects/`, and how the list is
+/// filtered.
+pub(in crate::app) struct ProjectsState {
+    pub(in crate::app) all: Vec<Project>,
+    /// Mirrors the `?tech=&status=&sort=` of the projects route.
+    filter: ProjectFilter,
+}
+
+impl Default for ProjectsState {
+    fn default() -> Self {
+        Self {
+            all: Project::embedded(),
+            filter: ProjectFilter::default(),
+        }
+    }
+}
+
+/// Filters and sort order of the projects page, kept in its URL query
+/// (`/projects?tech=rust&status=active&sort=newest`) so filtered views can be
+/// shared. `ProjectsState::filter` mirrors the query of the current route.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ProjectFilter {
+    /// Tag slug of a tech stack entry, e.g. `google-bigquery`.
+    pub tech: Option<String>,
+    pub status: Option<ProjectStatus>,
+    pub sort: ProjectSort,
+}
+
+impl ProjectFilter {
+    /// The filter in a route's query parameters (see [`crate::Route::query`]).
+    fn from_query(query: &[(String, String)]) -> Self {
+        let param = |key: &str| {
+            query
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+        };
+        Self {
+            tech: param("tech")
+                .map(frontmatter::tag_slug)
+                .filter(|tech| !tech.is_empty()),
+            status: param("status").and_then(ProjectStatus::from_slug),
+            sort: param("sort")
+                .and_then(ProjectSort::from_slug)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// `tech`, `status` and `sort`, with only the parameters that are set, or
+    /// none for the unfiltered list.
+    fn to_query(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+        if let Some(tech) = &self.tech {
+            params.push(("tech".to_string(), tech.clone()));
+        }
+        if let Some(status) = self.status {
+            params.push(("status".to_string(), status.slug().to_string()));
+        }
+        if self.sort != ProjectSort::default() {
+            params.push(("sort".to_string(), self.sort.slug().to_string()));
+        }
+        params
+    }
+
+    fn is_filtered(&self) -> bool {
+        self.tech.is_some() || self.status.is_some()
+    }
+
+    fn matches(&self, meta: &ProjectMeta) -> bool {
+        self.tech.as_deref().map_or(true, |tech| meta.uses(tech))
+            && self
+                .status
+                .map_or(true, |status| meta.status == Some(status))
+    }
+}
+
+impl MyApp {
+    fn show_projects(&mut self, ui: &mut egui::Ui) {
+        ui.add_space(24.0); // Top margin
+
+        let filter = self.projects.filter.clone();
+
+        // Calculate responsive margins
+        let screen_width = ui.available_width();
+        let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+
+        let tech = self.project_tech();
+        let statuses: Vec<ProjectStatus> = ProjectStatus::ALL
+            .into_iter()
+            .filter(|&status| {
+                self.projects
+                    .all
+                    .iter()
+                    .any(|project| project.meta.status == Some(status))
+            })
+            .collect();
+        let mut projects: Vec<&Project> = self
+            .projects
+            .all
+            .iter()
+            .filter(|project| filter.matches(&project.meta))
+            .collect();
+        projects.sort_by(|a, b| filter.sort.compare(&a.meta, &b.meta));
+        let mut navigate = None;
+        let mut refilter = None;
+
+        ui.horizontal(|ui| {
+            ui.add_space(left_margin); // Responsive left margin
+
+            ui.vertical(|ui| {
+                // Calculate the content width by subtracting both margins
+                let content_width = ui.available_width() - right_margin;
+                ui.set_max_width(content_width);
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
+                ui.label("Projects");
+
+                ui.add_space(8.0);
+                refilter = ui
+                    .scope(|ui| Self::project_filter_bar(ui, &filter, &tech, &statuses))
+                    .inner;
+
+                ui.add_space(32.0); // Design system H1 bottom margin
+
+                // Project entries with design system spacing
+                ui.spacing_mut().item_spacing.y = 32.0; // More space between projects
+
+                if projects.is_empty() {
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                    ui.label("No projects match these filters.");
+                }
+
+                for project in projects {
+                    let meta = &project.meta;
+                    ui.vertical(|ui| {
+                        ui.style_mut().override_text_style =
+                            Some(egui::TextStyle::Name("Heading2".into()));
+                        if ui.link(&meta.name).clicked() {
+                            navigate = Some(Page::Project(project.slug.clone()));
+                        }
+                        ui.add_space(8.0);
+                        ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                        ui.label(&meta.description);
+
+                        let details = Self::project_details(meta);
+                        if !details.is_empty() {
+                            ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                            ui.colored_label(egui::Color32::from_rgb(120, 120, 120), details);
+                        }
+                    });
+                }
+            });
+        });
+
+        if let Some(filter) = refilter {
+            self.route = Route {
+                page: Page::Projects,
+                query: filter.to_query(),
+                fragment: None,
+            };
+            self.projects.filter = filter;
+            self.push_route_to_browser();
+        } else if let Some(page) = navigate {
+            if self.navigate_to(page) {
+                self.push_route_to_browser();
+            }
+        }
+    }
+
+    /// Tech and status chips and the sort options above the project list.
+    /// Returns the new filter if any of them was clicked; clicking a selected
+    /// chip clears it.
+    fn project_filter_bar(
+        ui: &mut egui::Ui,
+        filter: &ProjectFilter,
+        tech: &[String],
+        statuses: &[ProjectStatus],
+    ) -> Option<ProjectFilter> {
+        let grey = egui::Color32::from_rgb(120, 120, 120);
+        let mut changed = None;
+
+        ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+        ui.spacing_mut().item_spacing = egui::vec2(6.0, 6.0);
+
+        if !tech.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                ui.colored_label(grey, "Tech:");
+                for name in tech {
+                    let slug = frontmatter::tag_slug(name);
+                    let selected = filter.tech.as_deref() == Some(slug.as_str());
+                    if ui.add(TagChip::plain(name, selected)).clicked() {
+                        changed = Some(ProjectFilter {
+                            tech: (!selected).then_some(slug),
+                            ..filter.clone()
+                        });
+                    }
+                }
+            });
+        }
+
+        if !statuses.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                ui.colored_label(grey, "Status:");
+                for &status in statuses {
+                    let selected = filter.status == Some(status);
+                    if ui
+                        .add(TagChip::plain(&status.to_string(), selected))
+                        .clicked()
+                    {
+                        changed = Some(ProjectFilter {
+                            status: (!selected).then_some(status),
+                            ..filter.clone()
+                        });
+                    }
+                }
+            });
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            ui.colored_label(grey, "Sort:");
+            for sort in ProjectSort::ALL {
+                if ui
+                    .selectable_label(filter.sort == sort, sort.label())
+                    .clicked()
+                    && filter.sort != sort
+                {
+                    changed = Some(ProjectFilter {
+                        sort,
+                        ..filter.clone()
+                    });
+                }
+            }
+            if filter.is_filtered() {
+                ui.add_space(8.0);
+                if ui.link("Clear filters").clicked() {
+                    changed = Some(ProjectFilter {
+                        sort: filter.sort,
+                        ..ProjectFilter::default()
+                    });
+                }
+            }
+        });
+
+        changed
+    }
+
+    /// Every tech stack entry across all projects, most used first.
+    fn project_tech(&self) -> Vec<String> {
+        let mut counts: Vec<(&String, usize)> = Vec::new();
+        for tech in self
+            .projects
+            .all
+            .iter()
+            .flat_map(|project| &project.meta.tech)
+        {
+            let slug = frontmatter::tag_slug(tech);
+            match counts
+                .iter_mut()
+                .find(|(existing, _)| frontmatter::tag_slug(existing) == slug)
+            {
+                Some((_, count)) => *count += 1,
+                None => counts.push((tech, 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        counts.into_iter().map(|(tech, _)| tech.clone()).collect()
+    }
+
+    /// A single project: its links, tech stack and markdown write-up.
+    fn show_project(&mut self, ui: &mut egui::Ui, slug: &str) {
+        let Some(project) = self
+            .projects
+            .all
+            .iter()
+            .find(|project| project.slug == slug)
+        else {
+            return;
+        };
+        let meta = &project.meta;
+        let mut back_to_list = false;
+
+        ui.add_space(16.0); // Top margin
+
+        let screen_width = ui.available_width();
+        let (left_margin, right_margin) = Self::calculate_responsive_margins(screen_width);
+
+        ui.horizontal(|ui| {
+            ui.add_space(left_margin); // Responsive left margin
+
+            ui.vertical(|ui| {
+                let content_width = ui.available_width() - right_margin;
+                ui.set_max_width(content_width);
+
+                if ui.button("< Back to Projects").clicked() {
+                    back_to_list = true;
+                }
+
+                ui.add_space(32.0);
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Name("Heading1".into()));
+                ui.label(&meta.name);
+
+                ui.add_space(12.0);
+
+                ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                ui.label(&meta.description);
+
+                let mut status = Vec::new();
+                status.extend(meta.status.map(|status| status.to_string()));
+                status.extend(meta.date_range());
+                if !status.is_empty() {
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                    ui.colored_label(egui::Color32::from_rgb(120, 120, 120), status.join(" · "));
+                }
+
+                let links = [
+                    ("Website", &meta.url),
+                    ("Source code", &meta.repo),
+                    ("Live demo", &meta.demo),
+                ];
+                if links.iter().any(|(_, url)| url.is_some()) {
+                    ui.add_space(8.0);
+                    ui.horizontal_wrapped(|ui| {
+                        ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                        ui.spacing_mut().item_spacing.x = 16.0;
+                        for (label, url) in links {
+                            if let Some(url) = url {
+                                ui.hyperlink_to(format!("{} ↗", label), url);
+                            }
+                        }
+                    });
+                }
+
+                if !meta.tech.is_empty() {
+                    ui.add_space(24.0);
+                    ui.style_mut().override_text_style =
+                        Some(egui::TextStyle::Name("Heading3".into()));
+                    ui.label("Tech stack");
+                    ui.style_mut().override_text_style = Some(egui::TextStyle::Body);
+                    for tech in &meta.tech {
+                        ui.label(format!("• {}", tech));
+                    }
+                }
+
+                if !project.content.trim().is_empty() {
+                    ui.add_space(40.0);
+
+                    ui.spacing_mut().item_spacing.y = 20.0;
+                    ui.spacing_mut().indent = 24.0;
+
+                    let viewer_id = format!("project_{}", project.slug.as_str());
+                    ui.scope(|ui| {
+                        ui.style_mut().override_text_style = Some(egui::TextStyle::Small);
+                        CommonMarkViewer::new(viewer_id).show(
+                            ui,
+                            &mut self.markdown_cache,
+                            &project.content,
+                        );
+                    });
+                }
+
+                ui.add_space(60.0);
+            });
+        });
+
+        if back_to_list && self.navigate_to(Page::Projects) {
+            self.push_route_to_browser();
+        }
+    }
+
+    /// Status, dates and tech stack of a project on one line.
+    fn project_details(meta: &ProjectMeta) -> String {
+        let mut details: Vec<String> = Vec::new();
+        if let Some(status) = meta.status {
+            details.push(status.to_string());
+        }
+        details.extend(meta.date_range());
+        if !meta.tech.is_empty() {
+            details.push(meta.tech.join(", "));
+        }
+        details.join(" · ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn project_filters_round_trip_through_the_query() {
+        let filters = [
+            ProjectFilter::default(),
+            ProjectFilter {
+                tech: Some("google-bigquery".to_string()),
+                status: Some(ProjectStatus::Active),
+                sort: ProjectSort::Alphabetical,
+            },
+            ProjectFilter {
+                sort: ProjectSort::Newest,
+                ..ProjectFilter::default()
+            },
+        ];
+        for filter in filters {
+            assert_eq!(ProjectFilter::from_query(&filter.to_query()), filter);
+        }
+        assert!(ProjectFilter::default().to_query().is_empty());
+
+        let route = Route::from_url("/projects?tech=Google%20BigQuery&status=paused&sort=name");
+        assert_eq!(
+            ProjectFilter::from_query(&route.query),
+            ProjectFilter {
+                tech: Some("google-bigquery".to_string()),
+                status: None,
+                sort: ProjectSort::Alphabetical,
+            }
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! Writes `rss.xml` and `atom.xml` for the public blog into the given
+//! directory. Trunk runs this after every build (see `Trunk.toml`) so the
+//! feeds end up next to `index.html` in the dist directory.
+//!
+//! Only `blog_posts/` is read, so private posts can never end up in a feed.
+//! Drafts and posts scheduled for later are left out too.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{env, fs};
+
+use chrono::Utc;
+use ps::feed::{self, FeedSite};
+use ps::{site, BlogPost};
+
+/// Absolute URL the site is served from, e.g. `https://example.com`.
+const SITE_URL_VAR: &str = "SITE_URL";
+
+fn main() -> ExitCode {
+    let Some(out_dir) = env::args_os().nth(1).map(PathBuf::from) else {
+        eprintln!("usage: feeds <output directory>");
+        return ExitCode::FAILURE;
+    };
+    let site_url = env::var(SITE_URL_VAR).ok();
+    let Some(base_url) = feed::base_url(site_url.as_deref()) else {
+        eprintln!(
+            "warning: {} is not set; not generating RSS/Atom feeds",
+            SITE_URL_VAR
+        );
+        return ExitCode::SUCCESS;
+    };
+
+    let posts_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("blog_posts");
+    let posts = match BlogPost::read_dir(&posts_dir) {
+        Ok(posts) => posts,
+        Err(err) => {
+            eprintln!("{}: {}", posts_dir.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let posts = feed::published(posts, Utc::now());
+
+    let config = site::embedded();
+    let description = format!("Blog posts by {}", config.author);
+    let site = FeedSite {
+        title: &config.title,
+        description: &description,
+        base_url,
+        author: &config.author,
+    };
+    for (file_name, xml) in [
+        ("rss.xml", feed::rss(&site, &posts)),
+        ("atom.xml", feed::atom(&site, &posts)),
+    ] {
+        let target = out_dir.join(file_name);
+        if let Err(err) = fs::write(&target, xml) {
+            eprintln!("{}: {}", target.display(), err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    println!(
+        "wrote {} post(s) to rss.xml and atom.xml in {}",
+        posts.len(),
+        out_dir.display()
+    );
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,76 @@
+//! Writes a static HTML copy of every public page into the given dist
+//! directory, using the `index.html` trunk built there as the template. Trunk
+//! runs this after every build (see `Trunk.toml`).
+//!
+//! Only `blog_posts/` is read, so private posts are never pre-rendered.
+//! Drafts and posts scheduled for later are left out too. In hash routing
+//! mode it also writes a `404.html` that redirects to the hash route.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{env, fs};
+
+use chrono::Utc;
+use ps::routing::RoutingMode;
+use ps::{feed, prerender, BlogPost, MarkdownPage, Project};
+
+/// Absolute URL the site is served from, e.g. `https://example.com`.
+const SITE_URL_VAR: &str = "SITE_URL";
+
+fn main() -> ExitCode {
+    let Some(dist_dir) = env::args_os().nth(1).map(PathBuf::from) else {
+        eprintln!("usage: prerender <dist directory>");
+        return ExitCode::FAILURE;
+    };
+    let site_url = env::var(SITE_URL_VAR).ok();
+    let site_url = feed::base_url(site_url.as_deref());
+
+    let template_path = dist_dir.join("index.html");
+    let template = match fs::read_to_string(&template_path) {
+        Ok(template) => template,
+        Err(err) => {
+            eprintln!("{}: {}", template_path.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let posts_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("blog_posts");
+    let posts = match BlogPost::read_dir(&posts_dir) {
+        Ok(posts) => posts,
+        Err(err) => {
+            eprintln!("{}: {}", posts_dir.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let posts = feed::published(posts, Utc::now());
+
+    let pages = prerender::pages(&posts, &Project::embedded(), MarkdownPage::embedded());
+    for page in &pages {
+        let target = dist_dir.join(page.file_path());
+        let html = prerender::render(&template, page, site_url);
+        let written = target
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(&target, html));
+        if let Err(err) = written {
+            eprintln!("{}: {}", target.display(), err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    // Without rewrites, the host answers any other path with `404.html`.
+    if RoutingMode::embedded() == RoutingMode::Hash {
+        let target = dist_dir.join("404.html");
+        if let Err(err) = fs::write(&target, prerender::not_found_redirect()) {
+            eprintln!("{}: {}", target.display(), err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    println!(
+        "pre-rendered {} page(s) into {}",
+        pages.len(),
+        dist_dir.display()
+    );
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,282 @@
+//! RSS 2.0 and Atom feeds for the public blog.
+//!
+//! Feeds are generated at build time by `src/bin/feeds.rs` from the same
+//! posts the app embeds, so they only ever see `blog_posts/`. Readers need
+//! absolute links, so everything is resolved against the site's base URL.
+
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Utc};
+
+use crate::html::{escape, markdown_to_html};
+use crate::{BlogPost, Page};
+
+/// What the feed says about the site itself.
+pub struct FeedSite<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    /// Absolute base URL without a trailing slash, e.g. `https://example.com`.
+    pub base_url: &'a str,
+    pub author: &'a str,
+}
+
+impl FeedSite<'_> {
+    fn url(&self, page: &Page) -> String {
+        format!("{}{}", self.base_url, page.to_url())
+    }
+}
+
+/// `SITE_URL` without a trailing slash, or `None` if it is unset or empty,
+/// in which case there is nothing to resolve links against and no feeds are
+/// written.
+pub fn base_url(site_url: Option<&str>) -> Option<&str> {
+    site_url
+        .filter(|url| !url.is_empty())
+        .map(|url| url.trim_end_matches('/'))
+}
+
+/// The posts that are public at `now`: no drafts, and nothing scheduled for
+/// later.
+pub fn published(posts: Vec<BlogPost>, now: DateTime<Utc>) -> Vec<BlogPost> {
+    posts
+        .into_iter()
+        .filter(|post| !post.meta.draft && post.published_at <= now)
+        .collect()
+}
+
+pub fn rss(site: &FeedSite<'_>, posts: &[BlogPost]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str("<channel>\n");
+    let _ = writeln!(xml, "  <title>{}</title>", escape(site.title));
+    let _ = writeln!(xml, "  <link>{}</link>", escape(&site.url(&Page::Blog)));
+    let _ = writeln!(
+        xml,
+        "  <atom:link href=\"{}/rss.xml\" rel=\"self\" type=\"application/rss+xml\"/>",
+        escape(site.base_url)
+    );
+    let _ = writeln!(
+        xml,
+        "  <description>{}</description>",
+        escape(site.description)
+    );
+    if let Some(latest) = last_updated(posts) {
+        let _ = writeln!(
+            xml,
+            "  <lastBuildDate>{}</lastBuildDate>",
+            latest.to_rfc2822()
+        );
+    }
+
+    for post in posts {
+        let url = site.url(&Page::BlogPost(post.slug.clone()));
+        xml.push_str("  <item>\n");
+        let _ = writeln!(xml, "    <title>{}</title>", escape(&post.title));
+        let _ = writeln!(xml, "    <link>{}</link>", escape(&url));
+        let _ = writeln!(
+            xml,
+            "    <guid isPermaLink=\"true\">{}</guid>",
+            escape(&url)
+        );
+        let _ = writeln!(
+            xml,
+            "    <pubDate>{}</pubDate>",
+            post.published_at.to_rfc2822()
+        );
+        for tag in &post.meta.tags {
+            let _ = writeln!(xml, "    <category>{}</category>", escape(tag));
+        }
+        let _ = writeln!(
+            xml,
+            "    <description>{}</description>",
+            escape(&markdown_to_html(&post.content, site.base_url))
+        );
+        xml.push_str("  </item>\n");
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
+
+pub fn atom(site: &FeedSite<'_>, posts: &[BlogPost]) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    let _ = writeln!(xml, "  <title>{}</title>", escape(site.title));
+    let _ = writeln!(xml, "  <subtitle>{}</subtitle>", escape(site.description));
+    let _ = writeln!(xml, "  <id>{}</id>", escape(&site.url(&Page::Blog)));
+    let _ = writeln!(xml, "  <link href=\"{}\"/>", escape(&site.url(&Page::Blog)));
+    let _ = writeln!(
+        xml,
+        "  <link href=\"{}/atom.xml\" rel=\"self\"/>",
+        escape(site.base_url)
+    );
+    let _ = writeln!(
+        xml,
+        "  <updated>{}</updated>",
+        last_updated(posts).unwrap_or_default().to_rfc3339()
+    );
+    let _ = writeln!(
+        xml,
+        "  <author><name>{}</name></author>",
+        escape(site.author)
+    );
+
+    for post in posts {
+        let url = site.url(&Page::BlogPost(post.slug.clone()));
+        let updated = post
+            .meta
+            .updated
+            .map_or(post.published_at, |updated| updated.with_timezone(&Utc));
+        xml.push_str("  <entry>\n");
+        let _ = writeln!(xml, "    <title>{}</title>", escape(&post.title));
+        let _ = writeln!(xml, "    <id>{}</id>", escape(&url));
+        let _ = writeln!(xml, "    <link href=\"{}\"/>", escape(&url));
+        let _ = writeln!(
+            xml,
+            "    <published>{}</published>",
+            post.published_at.to_rfc3339()
+        );
+        let _ = writeln!(xml, "    <updated>{}</updated>", updated.to_rfc3339());
+        if let Some(author) = &post.meta.author {
+            let _ = writeln!(xml, "    <author><name>{}</name></author>", escape(author));
+        }
+        for tag in &post.meta.tags {
+            let _ = writeln!(xml, "    <category term=\"{}\"/>", escape(tag));
+        }
+        if let Some(summary) = &post.meta.summary {
+            let _ = writeln!(xml, "    <summary>{}</summary>", escape(summary));
+        }
+        let _ = writeln!(
+            xml,
+            "    <content type=\"html\">{}</content>",
+            escape(&markdown_to_html(&post.content, site.base_url))
+        );
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// The most recent publication or update time among `posts`.
+fn last_updated(posts: &[BlogPost]) -> Option<DateTime<Utc>> {
+    posts
+        .iter()
+        .flat_map(|post| {
+            let updated = post.meta.updated.map(|updated| updated.with_timezone(&Utc));
+            std::iter::once(post.published_at).chain(updated)
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SITE: FeedSite<'static> = FeedSite {
+        title: "Tom & Jerry's <blog>",
+        description: "Cats \"and\" mice",
+        base_url: "https://example.com",
+        author: "Tom",
+    };
+
+    fn posts(files: &[(&str, &str)]) -> Vec<BlogPost> {
+        BlogPost::load_all(files.iter().copied())
+    }
+
+    fn escaped_post() -> Vec<BlogPost> {
+        let mut posts = posts(&[(
+            "post.md",
+            "---\n\
+             title: \"Fish & <chips> \\\"quoted\\\" isn't\"\n\
+             date: \"2025-03-01T09:30:00+01:00\"\n\
+             updated: \"2025-03-02T10:00:00Z\"\n\
+             summary: \"1 < 2 & 'three'\"\n\
+             ---\n\
+             Body with [a link](/blog/other).\n",
+        )]);
+        // Frontmatter tags are slugged; set them directly to check escaping.
+        posts[0].meta.tags = vec!["c&c".to_string(), "<b>".to_string()];
+        posts
+    }
+
+    #[test]
+    fn rss_escapes_titles_tags_and_content() {
+        let xml = rss(&SITE, &escaped_post());
+        assert!(xml.contains("<title>Tom &amp; Jerry&#39;s &lt;blog&gt;</title>"));
+        assert!(xml.contains("<description>Cats &quot;and&quot; mice</description>"));
+        assert!(
+            xml.contains("<title>Fish &amp; &lt;chips&gt; &quot;quoted&quot; isn&#39;t</title>")
+        );
+        assert!(xml.contains("<category>c&amp;c</category>"));
+        assert!(xml.contains("<category>&lt;b&gt;</category>"));
+        assert!(xml.contains("&lt;a href=&quot;https://example.com/blog/other&quot;&gt;"));
+        assert!(xml.contains("<link>https://example.com/blog/post</link>"));
+    }
+
+    #[test]
+    fn atom_escapes_titles_tags_and_summaries() {
+        let xml = atom(&SITE, &escaped_post());
+        assert!(xml.contains("<title>Tom &amp; Jerry&#39;s &lt;blog&gt;</title>"));
+        assert!(
+            xml.contains("<title>Fish &amp; &lt;chips&gt; &quot;quoted&quot; isn&#39;t</title>")
+        );
+        assert!(xml.contains("<category term=\"c&amp;c\"/>"));
+        assert!(xml.contains("<category term=\"&lt;b&gt;\"/>"));
+        assert!(xml.contains("<summary>1 &lt; 2 &amp; &#39;three&#39;</summary>"));
+        assert!(xml.contains("<author><name>Tom</name></author>"));
+    }
+
+    #[test]
+    fn dates_are_rfc_822_in_rss_and_rfc_3339_in_atom() {
+        let posts = escaped_post();
+        let rss = rss(&SITE, &posts);
+        assert!(rss.contains("<pubDate>Sat, 1 Mar 2025 08:30:00 +0000</pubDate>"));
+        assert!(rss.contains("<lastBuildDate>Sun, 2 Mar 2025 10:00:00 +0000</lastBuildDate>"));
+
+        let atom = atom(&SITE, &posts);
+        assert!(atom.contains("<published>2025-03-01T08:30:00+00:00</published>"));
+        assert!(atom.contains("<updated>2025-03-02T10:00:00+00:00</updated>"));
+    }
+
+    #[test]
+    fn drafts_and_scheduled_posts_are_not_published() {
+        let all = posts(&[
+            ("old.md", "---\ntitle: Old\ndate: 2025-01-01\n---\n"),
+            (
+                "due.md",
+                "---\ntitle: Due\ndate: \"2025-02-01T12:00:00Z\"\n---\n",
+            ),
+            (
+                "later.md",
+                "---\ntitle: Later\ndate: \"2025-02-01T12:00:01Z\"\n---\n",
+            ),
+            (
+                "draft.md",
+                "---\ntitle: Draft\ndate: 2025-01-01\ndraft: true\n---\n",
+            ),
+        ]);
+        let now = DateTime::parse_from_rfc3339("2025-02-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let slugs: Vec<String> = published(all, now)
+            .into_iter()
+            .map(|post| post.slug)
+            .collect();
+        assert_eq!(slugs, ["due", "old"]);
+    }
+
+    #[test]
+    fn feeds_need_a_site_url() {
+        assert_eq!(base_url(None), None);
+        assert_eq!(base_url(Some("")), None);
+        assert_eq!(
+            base_url(Some("https://example.com/")),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            base_url(Some("https://example.com/blog")),
+            Some("https://example.com/blog")
+        );
+    }
+}
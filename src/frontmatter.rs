@@ -0,0 +1,465 @@
+//! YAML frontmatter parsing for markdown posts.
+//!
+//! Every post starts with a `---` fenced YAML block that is deserialized into
+//! a typed [`PostMeta`]. Anything unexpected (unknown keys, unparseable dates,
+//! a missing title) is reported as a [`FrontmatterError`] pointing at the
+//! offending file and line, instead of silently falling back to defaults.
+//!
+//! This file is also compiled into `build.rs` so that malformed posts fail the
+//! build, which means it must only depend on `serde`, `serde_yaml` and `chrono`.
+
+use std::fmt;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+/// Typed frontmatter of a single post.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PostMeta {
+    pub title: String,
+    /// Publication time, keeping the offset it was written with so that the
+    /// displayed calendar date matches what the author typed.
+    pub date: DateTime<FixedOffset>,
+    /// Overrides the slug derived from the file name.
+    pub slug: Option<String>,
+    /// Lowercase, hyphenated and deduplicated; see [`tag_slug`].
+    pub tags: Vec<String>,
+    pub summary: Option<String>,
+    pub draft: bool,
+    pub updated: Option<DateTime<FixedOffset>>,
+    pub author: Option<String>,
+    pub cover_image: Option<String>,
+    /// Access group of a private post (normalised like tags). Each group is
+    /// encrypted under its own password; `None` means the main password only.
+    pub access: Option<String>,
+}
+
+impl PostMeta {
+    pub fn published_at(&self) -> DateTime<Utc> {
+        self.date.with_timezone(&Utc)
+    }
+}
+
+/// The frontmatter exactly as written in the file, before validation.
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawMeta {
+    title: Option<String>,
+    date: Option<String>,
+    slug: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    summary: Option<String>,
+    #[serde(default)]
+    draft: bool,
+    updated: Option<String>,
+    author: Option<String>,
+    #[serde(alias = "cover")]
+    cover_image: Option<String>,
+    access: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FrontmatterErrorKind {
+    /// The file does not start with a `---` line.
+    Missing,
+    /// The opening `---` has no matching closing `---`.
+    Unterminated,
+    /// The block is not valid YAML, or does not match the schema.
+    Yaml(String),
+    MissingField(&'static str),
+    InvalidDate {
+        field: &'static str,
+        value: String,
+    },
+}
+
+/// A frontmatter problem, located by file name and 1-based line number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrontmatterError {
+    pub file: String,
+    pub line: Option<usize>,
+    pub kind: FrontmatterErrorKind,
+}
+
+impl fmt::Display for FrontmatterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: ", self.file, line)?,
+            None => write!(f, "{}: ", self.file)?,
+        }
+        match &self.kind {
+            FrontmatterErrorKind::Missing => {
+                write!(f, "missing frontmatter (expected a leading `---` block)")
+            }
+            FrontmatterErrorKind::Unterminated => {
+                write!(f, "frontmatter is not closed by a `---` line")
+            }
+            FrontmatterErrorKind::Yaml(message) => write!(f, "invalid frontmatter: {}", message),
+            FrontmatterErrorKind::MissingField(field) => {
+                write!(f, "missing required field `{}`", field)
+            }
+            FrontmatterErrorKind::InvalidDate { field, value } => {
+                write!(f, "`{}` is not a recognised date: {:?}", field, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FrontmatterError {}
+
+/// Split a markdown file into its frontmatter and body and parse the former.
+///
+/// `file` is only used for error messages. CRLF line endings are accepted and
+/// the returned body always uses `\n`.
+pub fn parse(file: &str, content: &str) -> Result<(PostMeta, String), FrontmatterError> {
+    let block = Block::split(file, content)?;
+    if block.is_empty() {
+        return Err(block.error(Some(2), FrontmatterErrorKind::MissingField("title")));
+    }
+    let raw: RawMeta = block.deserialize()?;
+
+    let title = match raw.title {
+        Some(title) if !title.trim().is_empty() => title.trim().to_string(),
+        _ => {
+            return Err(block.error(
+                block.key_line("title"),
+                FrontmatterErrorKind::MissingField("title"),
+            ))
+        }
+    };
+
+    let Some(date_value) = raw.date else {
+        return Err(block.error(None, FrontmatterErrorKind::MissingField("date")));
+    };
+    let date = block.date("date", &date_value)?;
+    let updated = match raw.updated {
+        Some(value) => Some(block.date("updated", &value)?),
+        None => None,
+    };
+
+    let meta = PostMeta {
+        title,
+        date,
+        slug: non_empty(raw.slug),
+        tags: normalize_tags(raw.tags),
+        summary: non_empty(raw.summary),
+        draft: raw.draft,
+        updated,
+        author: non_empty(raw.author),
+        cover_image: non_empty(raw.cover_image),
+        access: raw
+            .access
+            .map(|group| tag_slug(&group))
+            .filter(|group| !group.is_empty()),
+    };
+
+    Ok((meta, block.body))
+}
+
+/// A file split at its `---` fences, for parsing other kinds of frontmatter
+/// (see `src/projects.rs`) with the same error reporting as posts.
+pub struct Block {
+    file: String,
+    yaml: String,
+    pub body: String,
+}
+
+impl Block {
+    pub fn split(file: &str, content: &str) -> Result<Self, FrontmatterError> {
+        let error = |line: Option<usize>, kind| FrontmatterError {
+            file: file.to_string(),
+            line,
+            kind,
+        };
+
+        let normalized = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+        let mut lines = normalized.split_inclusive('\n');
+
+        let Some(opening) = lines.next().filter(|first| first.trim_end() == "---") else {
+            return Err(error(Some(1), FrontmatterErrorKind::Missing));
+        };
+
+        // Line 1 is the opening fence, so YAML line `n` is file line `n + 1`.
+        let mut yaml = String::new();
+        let mut body_offset = None;
+        let mut consumed = opening.len();
+        for line in lines {
+            if line.trim_end() == "---" {
+                body_offset = Some(consumed + line.len());
+                break;
+            }
+            yaml.push_str(line);
+            consumed += line.len();
+        }
+        let Some(body_offset) = body_offset else {
+            return Err(error(Some(1), FrontmatterErrorKind::Unterminated));
+        };
+        let body = normalized[body_offset..].to_string();
+
+        Ok(Self {
+            file: file.to_string(),
+            yaml,
+            body,
+        })
+    }
+
+    /// Whether there is nothing between the fences.
+    pub fn is_empty(&self) -> bool {
+        self.yaml.trim().is_empty()
+    }
+
+    pub fn deserialize<T: serde::de::DeserializeOwned>(&self) -> Result<T, FrontmatterError> {
+        serde_yaml::from_str(&self.yaml).map_err(|err| {
+            let line = err.location().map(|location| location.line() + 1);
+            // serde_yaml appends a position relative to the YAML block; we report
+            // the file line instead.
+            let message = err.to_string();
+            let message = match message.rsplit_once(" at line ") {
+                Some((message, _)) if line.is_some() => message.to_string(),
+                _ => message,
+            };
+            self.error(line, FrontmatterErrorKind::Yaml(message))
+        })
+    }
+
+    pub fn error(&self, line: Option<usize>, kind: FrontmatterErrorKind) -> FrontmatterError {
+        FrontmatterError {
+            file: self.file.clone(),
+            line,
+            kind,
+        }
+    }
+
+    /// The file line on which `key` is set, if it is a top-level key.
+    pub fn key_line(&self, key: &str) -> Option<usize> {
+        self.yaml
+            .lines()
+            .position(|line| line.starts_with(key) && line[key.len()..].starts_with(':'))
+            .map(|index| index + 2)
+    }
+
+    /// Parse the value of the date field `field`.
+    pub fn date(
+        &self,
+        field: &'static str,
+        value: &str,
+    ) -> Result<DateTime<FixedOffset>, FrontmatterError> {
+        parse_date(value).ok_or_else(|| {
+            self.error(
+                self.key_line(field),
+                FrontmatterErrorKind::InvalidDate {
+                    field,
+                    value: value.to_string(),
+                },
+            )
+        })
+    }
+}
+
+/// Trimmed, with blank values treated as missing.
+pub fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Canonical form of a tag, used both for display and in `/blog/tag/{tag}`
+/// URLs: lowercase ASCII alphanumerics separated by single hyphens.
+pub fn tag_slug(tag: &str) -> String {
+    let mut slug = String::new();
+    for ch in tag.trim().chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Whether `slug` can be used as a path segment as-is: ASCII letters,
+/// digits, `-` and `_`, nothing else.
+pub fn is_url_safe_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|tag| tag_slug(tag)) {
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+/// Parse the date formats we accept in frontmatter. Values without an offset
+/// are interpreted as UTC.
+pub fn parse_date(value: &str) -> Option<DateTime<FixedOffset>> {
+    let value = value.trim();
+    let utc = FixedOffset::east_opt(0)?;
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Some(dt);
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(utc.from_utc_datetime(&dt));
+        }
+    }
+    for format in ["%Y-%m-%d", "%B %d, %Y"] {
+        if let Ok(date) = NaiveDate::parse_from_str(value, format) {
+            return Some(utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn body_follows_an_opening_fence_with_trailing_whitespace() {
+        let (meta, body) =
+            parse("post.md", "---   \ntitle: a\ndate: 2025-01-01\n---\nBody\n").unwrap();
+        assert_eq!(meta.title, "a");
+        assert_eq!(body, "Body\n");
+    }
+
+    #[test]
+    fn crlf_line_endings_are_accepted() {
+        let (meta, body) = parse(
+            "post.md",
+            "---\r\ntitle: a\r\ndate: 2025-01-01\r\n---\r\nBody\r\nMore\r\n",
+        )
+        .unwrap();
+        assert_eq!(meta.title, "a");
+        assert_eq!(body, "Body\nMore\n");
+    }
+
+    fn error(content: &str) -> (Option<usize>, FrontmatterErrorKind) {
+        let err = parse("post.md", content).unwrap_err();
+        assert_eq!(err.file, "post.md");
+        (err.line, err.kind)
+    }
+
+    #[test]
+    fn parses_every_field() {
+        let (meta, body) = parse(
+            "post.md",
+            "---\ntitle: \" Hello \"\ndate: 2025-10-29T22:30:00-05:00\nslug: hi\n\
+             tags: [Rust, Web Dev, rust]\nsummary: Short\ndraft: true\n\
+             updated: 2025-11-01\nauthor: Me\ncover: c.png\naccess: Close Friends\n---\n\nBody\n",
+        )
+        .unwrap();
+        assert_eq!(meta.title, "Hello");
+        assert_eq!(meta.date.to_rfc3339(), "2025-10-29T22:30:00-05:00");
+        assert_eq!(meta.slug.as_deref(), Some("hi"));
+        assert_eq!(meta.tags, ["rust", "web-dev"]);
+        assert_eq!(meta.summary.as_deref(), Some("Short"));
+        assert!(meta.draft);
+        assert_eq!(meta.updated, parse_date("2025-11-01"));
+        assert_eq!(meta.author.as_deref(), Some("Me"));
+        assert_eq!(meta.cover_image.as_deref(), Some("c.png"));
+        assert_eq!(meta.access.as_deref(), Some("close-friends"));
+        assert_eq!(body, "\nBody\n");
+    }
+
+    #[test]
+    fn reports_missing_and_unterminated_fences() {
+        assert_eq!(error("# Title\n"), (Some(1), FrontmatterErrorKind::Missing));
+        assert_eq!(
+            error("---\ntitle: a\n"),
+            (Some(1), FrontmatterErrorKind::Unterminated)
+        );
+    }
+
+    #[test]
+    fn reports_missing_fields() {
+        assert_eq!(
+            error("---\n---\n"),
+            (Some(2), FrontmatterErrorKind::MissingField("title"))
+        );
+        assert_eq!(
+            error("---\ndate: 2025-01-01\ntitle: \"  \"\n---\n"),
+            (Some(3), FrontmatterErrorKind::MissingField("title"))
+        );
+        assert_eq!(
+            error("---\ntitle: a\n---\n"),
+            (None, FrontmatterErrorKind::MissingField("date"))
+        );
+    }
+
+    #[test]
+    fn reports_invalid_dates_on_their_line() {
+        assert_eq!(
+            error("---\ntitle: a\ndate: 2025-01-01\nupdated: yesterday\n---\n"),
+            (
+                Some(4),
+                FrontmatterErrorKind::InvalidDate {
+                    field: "updated",
+                    value: "yesterday".to_string(),
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn reports_unknown_keys_on_their_file_line() {
+        let (line, kind) = error("---\ntitle: a\ndate: 2025-01-01\ncategory: x\n---\n");
+        assert_eq!(line, Some(4));
+        assert!(
+            matches!(&kind, FrontmatterErrorKind::Yaml(message) if message.contains("category")),
+            "{:?}",
+            kind
+        );
+    }
+
+    #[test]
+    fn tag_slug_is_lowercase_and_hyphenated() {
+        assert_eq!(tag_slug("Information Theory"), "information-theory");
+        assert_eq!(tag_slug("  C++ / Rust! "), "c-rust");
+        assert_eq!(tag_slug("web--dev"), "web-dev");
+        assert_eq!(tag_slug("日本"), "");
+    }
+
+    #[test]
+    fn parse_date_accepts_the_documented_formats() {
+        let utc = |value| parse_date(value).map(|date| date.with_timezone(&Utc).to_rfc3339());
+        assert_eq!(
+            utc("2025-10-29T22:30:00-05:00").as_deref(),
+            Some("2025-10-30T03:30:00+00:00")
+        );
+        assert_eq!(
+            utc("2025-10-29T22:30:00").as_deref(),
+            Some("2025-10-29T22:30:00+00:00")
+        );
+        assert_eq!(
+            utc("2025-10-29 22:30:00").as_deref(),
+            Some("2025-10-29T22:30:00+00:00")
+        );
+        assert_eq!(
+            utc("2025-10-29").as_deref(),
+            Some("2025-10-29T00:00:00+00:00")
+        );
+        assert_eq!(
+            utc("October 29, 2025").as_deref(),
+            Some("2025-10-29T00:00:00+00:00")
+        );
+        assert_eq!(utc("29/10/2025"), None);
+        assert_eq!(utc("2025-02-30"), None);
+    }
+
+    #[test]
+    fn url_safe_slugs() {
+        assert!(is_url_safe_slug("2025-10-29-my_post"));
+        assert!(!is_url_safe_slug(""));
+        assert!(!is_url_safe_slug("my post"));
+        assert!(!is_url_safe_slug("a/b"));
+        assert!(!is_url_safe_slug("café"));
+    }
+}
@@ -0,0 +1,244 @@
+//! Headings of a markdown document and the anchors that link to them
+//! (`/blog/my-post#why-rust`).
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use crate::frontmatter::tag_slug;
+
+/// The markdown extensions posts are parsed with, here and in
+/// [`crate::html`], so both agree on where the headings are.
+pub const PARSE_OPTIONS: Options = Options::ENABLE_TABLES
+    .union(Options::ENABLE_STRIKETHROUGH)
+    .union(Options::ENABLE_TASKLISTS)
+    .union(Options::ENABLE_FOOTNOTES);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Heading {
+    /// 1 for `#`, up to 6.
+    pub level: u8,
+    /// The heading as plain text, without markup.
+    pub text: String,
+    /// Unique within the document, e.g. `why-rust` or `notes-1` for a second
+    /// "Notes".
+    pub anchor: String,
+    /// Byte offset of the heading in the markdown.
+    pub offset: usize,
+}
+
+/// Every heading in `markdown`, in order. Lines in code blocks that merely
+/// look like headings are skipped.
+pub fn headings(markdown: &str) -> Vec<Heading> {
+    let mut headings: Vec<Heading> = Vec::new();
+    let mut current: Option<(u8, usize, String)> = None;
+    for (event, range) in Parser::new_ext(markdown, PARSE_OPTIONS).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                current = Some((level_number(level), range.start, String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, _, heading_text)) = &mut current {
+                    heading_text.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some((level, offset, text)) = current.take() {
+                    let text = text.trim().to_string();
+                    let anchor = unique_anchor(&text, &headings);
+                    headings.push(Heading {
+                        level,
+                        text,
+                        anchor,
+                        offset,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    headings
+}
+
+/// A document's headings and the pieces it is drawn in, worked out once
+/// when it is loaded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Outline {
+    pub headings: Vec<Heading>,
+    pub parts: Vec<Part>,
+}
+
+impl Outline {
+    pub fn of(markdown: &str) -> Self {
+        let headings = headings(markdown);
+        let parts = split_at_headings(markdown, &headings);
+        Self { headings, parts }
+    }
+}
+
+/// A piece of a document cut by [`split_at_headings`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Part {
+    /// The piece's markdown, followed by every link reference definition of
+    /// the document (which render as nothing) so its `[text][ref]` links
+    /// still resolve.
+    pub markdown: String,
+    /// The headings in this piece, as indices into those it was cut at.
+    pub headings: Range<usize>,
+}
+
+/// `markdown` cut into pieces that render on their own as they would within
+/// the whole document, so each can be drawn (and scrolled to) separately.
+///
+/// Cuts fall just before top-level `#` headings only: not before headings in
+/// block quotes or lists, whose markers would stay behind, nor between a
+/// footnote and its definition. Blank pieces are left out.
+pub fn split_at_headings(markdown: &str, headings: &[Heading]) -> Vec<Part> {
+    let parser = Parser::new_ext(markdown, PARSE_OPTIONS).into_offset_iter();
+    let link_definitions: Vec<&str> = parser
+        .reference_definitions()
+        .iter()
+        .map(|(_, definition)| &markdown[definition.span.clone()])
+        .collect();
+
+    let mut depth = 0;
+    let mut cuts = Vec::new();
+    // First and last offset at which each footnote is referenced or defined.
+    let mut footnotes: BTreeMap<String, Range<usize>> = BTreeMap::new();
+    for (event, range) in parser {
+        let footnote = match &event {
+            Event::FootnoteReference(label) | Event::Start(Tag::FootnoteDefinition(label)) => {
+                Some(label.to_string())
+            }
+            _ => None,
+        };
+        if let Some(label) = footnote {
+            let span = footnotes.entry(label).or_insert(range.clone());
+            span.start = span.start.min(range.start);
+            span.end = span.end.max(range.end);
+        }
+
+        match event {
+            Event::Start(Tag::BlockQuote | Tag::List(_) | Tag::FootnoteDefinition(_)) => {
+                depth += 1;
+            }
+            Event::End(TagEnd::BlockQuote | TagEnd::List(_) | TagEnd::FootnoteDefinition) => {
+                depth -= 1;
+            }
+            // Setext headings (underlined with `===` or `---`) start at their
+            // text, which can't be told from a paragraph's start.
+            Event::Start(Tag::Heading { .. })
+                if depth == 0 && markdown[range.start..].starts_with('#') =>
+            {
+                cuts.push(range.start);
+            }
+            _ => {}
+        }
+    }
+    cuts.retain(|&cut| {
+        !footnotes
+            .values()
+            .any(|span| span.start < cut && cut < span.end)
+    });
+
+    let mut parts = Vec::with_capacity(cuts.len() + 1);
+    let mut start = 0;
+    let mut first_heading = 0;
+    for end in cuts.into_iter().chain(std::iter::once(markdown.len())) {
+        let text = &markdown[start..end];
+        let next_heading = first_heading
+            + headings[first_heading..]
+                .iter()
+                .take_while(|heading| heading.offset < end)
+                .count();
+        if !text.trim().is_empty() {
+            let mut markdown = text.to_string();
+            for definition in &link_definitions {
+                markdown.push_str("\n\n");
+                markdown.push_str(definition);
+            }
+            parts.push(Part {
+                markdown,
+                headings: first_heading..next_heading,
+            });
+        }
+        start = end;
+        first_heading = next_heading;
+    }
+    parts
+}
+
+/// The slug of `text`, numbered like GitHub does (`notes`, `notes-1`, ...)
+/// if an earlier heading already has it.
+fn unique_anchor(text: &str, earlier: &[Heading]) -> String {
+    let base = match tag_slug(text) {
+        slug if slug.is_empty() => "section".to_string(),
+        slug => slug,
+    };
+    let taken = |anchor: &str| earlier.iter().any(|heading| heading.anchor == anchor);
+    if !taken(&base) {
+        return base;
+    }
+    (1..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|anchor| !taken(anchor))
+        .expect("some suffix is free")
+}
+
+fn level_number(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(markdown: &str) -> Vec<Part> {
+        split_at_headings(markdown, &headings(markdown))
+    }
+
+    #[test]
+    fn cuts_before_top_level_headings() {
+        let parts = split("Intro\n\n# One\n\nText\n\n## Two\n");
+        let texts: Vec<&str> = parts.iter().map(|part| part.markdown.as_str()).collect();
+        assert_eq!(texts, ["Intro\n\n", "# One\n\nText\n\n", "## Two\n"]);
+        assert_eq!(parts[1].headings, 0..1);
+        assert_eq!(parts[2].headings, 1..2);
+    }
+
+    #[test]
+    fn keeps_quoted_and_listed_headings_with_their_markers() {
+        let parts = split("# One\n\n> ## Quoted\n\n- ## Listed\n");
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].headings, 0..3);
+    }
+
+    #[test]
+    fn every_part_gets_the_link_definitions() {
+        let parts = split("# One\n\nSee [docs][d].\n\n# Two\n\n[d]: https://example.com\n");
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].markdown.ends_with("\n\n[d]: https://example.com"));
+    }
+
+    #[test]
+    fn never_cuts_between_a_footnote_and_its_definition() {
+        let parts = split("# One\n\nClaim[^1]\n\n# Two\n\n# Notes\n\n[^1]: Source\n\n# After\n");
+        let texts: Vec<&str> = parts.iter().map(|part| part.markdown.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "# One\n\nClaim[^1]\n\n# Two\n\n# Notes\n\n[^1]: Source\n\n",
+                "# After\n"
+            ]
+        );
+        assert_eq!(parts[0].headings, 0..3);
+    }
+}
@@ -0,0 +1,212 @@
+//! Markdown to HTML for the static outputs (feeds and pre-rendered pages).
+//!
+//! The app itself draws markdown with egui_commonmark; this is only used by the
+//! native tools in `src/bin/`.
+
+use std::fmt::Write as _;
+
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag, TagEnd};
+
+use crate::headings::{self, PARSE_OPTIONS};
+
+/// Render a post body to HTML. Root-relative links and images are made
+/// absolute against `base_url` (which may be empty) so they still work
+/// inside a feed reader. Headings get the same `id`s as the app's anchors,
+/// so `/blog/my-post#why-rust` works on the pre-rendered page too.
+pub fn markdown_to_html(markdown: &str, base_url: &str) -> String {
+    let mut anchors = headings::headings(markdown)
+        .into_iter()
+        .map(|heading| heading.anchor);
+    let absolute = |url: &str| {
+        if url.starts_with('/') && !url.starts_with("//") {
+            format!("{}{}", base_url, url)
+        } else {
+            url.to_string()
+        }
+    };
+
+    let mut html = String::with_capacity(markdown.len() * 3 / 2);
+    // Inside an image, text becomes the alt attribute and markup is dropped.
+    let mut in_image = false;
+    let mut in_table_head = false;
+
+    for event in Parser::new_ext(markdown, PARSE_OPTIONS) {
+        if in_image {
+            match event {
+                Event::Text(text) | Event::Code(text) => html.push_str(&escape(&text)),
+                Event::End(TagEnd::Image) => {
+                    html.push_str("\" />");
+                    in_image = false;
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Paragraph => html.push_str("<p>"),
+                Tag::Heading { level, .. } => match anchors.next() {
+                    Some(anchor) => {
+                        let _ = write!(html, "<{} id=\"{}\">", level, escape(&anchor));
+                    }
+                    None => {
+                        let _ = write!(html, "<{}>", level);
+                    }
+                },
+                Tag::BlockQuote => html.push_str("<blockquote>\n"),
+                Tag::CodeBlock(CodeBlockKind::Fenced(lang)) if !lang.is_empty() => {
+                    let lang = lang.split_whitespace().next().unwrap_or_default();
+                    let _ = write!(html, "<pre><code class=\"language-{}\">", escape(lang));
+                }
+                Tag::CodeBlock(_) => html.push_str("<pre><code>"),
+                Tag::HtmlBlock | Tag::MetadataBlock(_) => {}
+                Tag::List(Some(1)) => html.push_str("<ol>\n"),
+                Tag::List(Some(start)) => {
+                    let _ = writeln!(html, "<ol start=\"{}\">", start);
+                }
+                Tag::List(None) => html.push_str("<ul>\n"),
+                Tag::Item => html.push_str("<li>"),
+                Tag::FootnoteDefinition(name) => {
+                    let _ = write!(
+                        html,
+                        "<div class=\"footnote\" id=\"fn-{}\"><sup>{}</sup> ",
+                        escape(&name),
+                        escape(&name)
+                    );
+                }
+                Tag::Table(_) => html.push_str("<table>\n"),
+                Tag::TableHead => {
+                    in_table_head = true;
+                    html.push_str("<thead><tr>");
+                }
+                Tag::TableRow => html.push_str("<tr>"),
+                Tag::TableCell => html.push_str(if in_table_head { "<th>" } else { "<td>" }),
+                Tag::Emphasis => html.push_str("<em>"),
+                Tag::Strong => html.push_str("<strong>"),
+                Tag::Strikethrough => html.push_str("<del>"),
+                Tag::Link {
+                    dest_url, title, ..
+                } => {
+                    let _ = write!(html, "<a href=\"{}\"", escape(&absolute(&dest_url)));
+                    if !title.is_empty() {
+                        let _ = write!(html, " title=\"{}\"", escape(&title));
+                    }
+                    html.push('>');
+                }
+                Tag::Image { dest_url, .. } => {
+                    let _ = write!(html, "<img src=\"{}\" alt=\"", escape(&absolute(&dest_url)));
+                    in_image = true;
+                }
+            },
+            Event::End(tag) => match tag {
+                TagEnd::Paragraph => html.push_str("</p>\n"),
+                TagEnd::Heading(level) => {
+                    let _ = writeln!(html, "</{}>", level);
+                }
+                TagEnd::BlockQuote => html.push_str("</blockquote>\n"),
+                TagEnd::CodeBlock => html.push_str("</code></pre>\n"),
+                TagEnd::HtmlBlock | TagEnd::MetadataBlock(_) => {}
+                TagEnd::List(true) => html.push_str("</ol>\n"),
+                TagEnd::List(false) => html.push_str("</ul>\n"),
+                TagEnd::Item => html.push_str("</li>\n"),
+                TagEnd::FootnoteDefinition => html.push_str("</div>\n"),
+                TagEnd::Table => html.push_str("</tbody></table>\n"),
+                TagEnd::TableHead => {
+                    in_table_head = false;
+                    html.push_str("</tr></thead><tbody>\n");
+                }
+                TagEnd::TableRow => html.push_str("</tr>\n"),
+                TagEnd::TableCell => html.push_str(if in_table_head { "</th>" } else { "</td>" }),
+                TagEnd::Emphasis => html.push_str("</em>"),
+                TagEnd::Strong => html.push_str("</strong>"),
+                TagEnd::Strikethrough => html.push_str("</del>"),
+                TagEnd::Link => html.push_str("</a>"),
+                TagEnd::Image => {}
+            },
+            Event::Text(text) => html.push_str(&escape(&text)),
+            Event::Code(code) => {
+                let _ = write!(html, "<code>{}</code>", escape(&code));
+            }
+            Event::Html(raw) | Event::InlineHtml(raw) => html.push_str(&raw),
+            Event::FootnoteReference(name) => {
+                let _ = write!(
+                    html,
+                    "<sup><a href=\"#fn-{}\">{}</a></sup>",
+                    escape(&name),
+                    escape(&name)
+                );
+            }
+            Event::SoftBreak => html.push('\n'),
+            Event::HardBreak => html.push_str("<br />\n"),
+            Event::Rule => html.push_str("<hr />\n"),
+            Event::TaskListMarker(checked) => html.push_str(if checked {
+                "<input type=\"checkbox\" disabled checked /> "
+            } else {
+                "<input type=\"checkbox\" disabled /> "
+            }),
+        }
+    }
+    html
+}
+
+/// Escape text for use in XML/HTML content and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_every_markup_character() {
+        assert_eq!(
+            escape(r#"Tom & Jerry's <b>"best"</b>"#),
+            "Tom &amp; Jerry&#39;s &lt;b&gt;&quot;best&quot;&lt;/b&gt;"
+        );
+        assert_eq!(escape("日本語 ok"), "日本語 ok");
+    }
+
+    #[test]
+    fn text_code_and_attributes_are_escaped() {
+        let html = markdown_to_html(
+            "A & B < C `x > \"y\"`\n\n```rust\"><script>\nlet s = '<';\n```\n\n![a \"cat\" & 1 < 2](/cat.png)\n",
+            "",
+        );
+        assert!(html.contains("<p>A &amp; B &lt; C <code>x &gt; &quot;y&quot;</code></p>"));
+        assert!(html.contains("<pre><code class=\"language-rust&quot;&gt;&lt;script&gt;\">"));
+        assert!(html.contains("let s = &#39;&lt;&#39;;"));
+        assert!(html.contains("alt=\"a &quot;cat&quot; &amp; 1 &lt; 2\""));
+    }
+
+    #[test]
+    fn root_relative_links_become_absolute() {
+        let html = markdown_to_html(
+            "[post](/blog/a?x=1&y=2) [cdn](//cdn.example.com/a) [rel](b) ![img](/a.png)",
+            "https://example.com",
+        );
+        assert!(html.contains("<a href=\"https://example.com/blog/a?x=1&amp;y=2\">"));
+        assert!(html.contains("<a href=\"//cdn.example.com/a\">"));
+        assert!(html.contains("<a href=\"b\">"));
+        assert!(html.contains("<img src=\"https://example.com/a.png\""));
+    }
+
+    #[test]
+    fn headings_get_the_app_anchors() {
+        let html = markdown_to_html("# Why Rust?\n\ntext\n\n## Why Rust?\n", "");
+        assert!(html.contains("<h1 id=\"why-rust\">Why Rust?</h1>"));
+        assert!(html.contains("<h2 id=\"why-rust-1\">Why Rust?</h2>"));
+    }
+}
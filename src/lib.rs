@@ -1,4 +1,17 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
-pub use app::MyApp;
+pub mod feed;
+pub mod frontmatter;
+pub mod headings;
+pub mod html;
+pub mod nav;
+pub mod pages;
+pub mod prerender;
+pub mod projects;
+pub mod routing;
+pub mod search;
+pub mod site;
+pub mod stats;
+pub mod vault;
+pub use app::{BlogPost, MarkdownPage, MyApp, Page, Project, ProjectFilter, Route};
@@ -6,6 +6,7 @@
 fn main() -> eframe::Result<()> {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
 
+    let title = &ps::site::embedded().title;
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([400.0, 300.0])
@@ -18,7 +19,7 @@ fn main() -> eframe::Result<()> {
         ..Default::default()
     };
     eframe::run_native(
-        "eframe template",
+        title,
         native_options,
         Box::new(|cc| Box::new(ps::MyApp::new(cc))),
     )
@@ -44,5 +45,13 @@ fn main() {
             )
             .await
             .expect("failed to start eframe");
+
+        // The app has taken over; drop the static copy of the page (see src/prerender.rs).
+        if let Some(prerendered) = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id("prerendered"))
+        {
+            prerendered.remove();
+        }
     });
 }
@@ -0,0 +1,195 @@
+//! The top navigation.
+//!
+//! The desktop nav bar, the mobile menu and the pre-rendered pages all draw
+//! from one [`NavRegistry`], so adding a section means adding one entry.
+//! Entries come from `[[nav]]` in `site.toml`; more can be
+//! [registered](NavRegistry::register) at startup.
+
+use crate::site::{self, NavLink, NavSection, NavVisibility, SiteConfig};
+use crate::Page;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NavItem {
+    pub label: String,
+    pub target: NavTarget,
+    pub visibility: NavVisibility,
+    /// Lower comes first; entries without one count as 0, and ties keep
+    /// registration order.
+    pub order: Option<i32>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum NavTarget {
+    Page(Page),
+    /// Another site, opened in a new tab.
+    External(String),
+}
+
+impl NavItem {
+    /// The label as drawn, with a marker on external links.
+    pub fn display_label(&self) -> String {
+        match self.target {
+            NavTarget::Page(_) => self.label.clone(),
+            NavTarget::External(_) => format!("{} ↗", self.label),
+        }
+    }
+
+    /// Whether the entry is highlighted while `current` is shown.
+    pub fn is_active(&self, current: &Page) -> bool {
+        match &self.target {
+            NavTarget::Page(page) => current.is_within(page),
+            NavTarget::External(_) => false,
+        }
+    }
+
+    pub fn is_visible(&self, private_unlocked: bool) -> bool {
+        match self.visibility {
+            NavVisibility::Always => true,
+            NavVisibility::Hidden => false,
+            NavVisibility::Unlocked => private_unlocked,
+        }
+    }
+
+    /// The URL the entry points at, for links outside the app.
+    pub fn href(&self) -> String {
+        match &self.target {
+            NavTarget::Page(page) => page.to_url(),
+            NavTarget::External(url) => url.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct NavRegistry {
+    items: Vec<NavItem>,
+}
+
+impl NavRegistry {
+    /// The entries of the embedded `site.toml`.
+    pub fn embedded() -> Self {
+        Self::from_config(site::embedded())
+    }
+
+    pub fn from_config(config: &SiteConfig) -> Self {
+        let mut registry = Self::default();
+        for entry in &config.nav {
+            registry.register(NavItem {
+                label: entry.label.clone(),
+                target: match &entry.link {
+                    NavLink::Section(section) => NavTarget::Page(section_page(*section)),
+                    NavLink::External(url) => NavTarget::External(url.clone()),
+                },
+                visibility: entry.visibility,
+                order: entry.order,
+            });
+        }
+        registry
+    }
+
+    pub fn register(&mut self, item: NavItem) {
+        self.items.push(item);
+        // Stable, so entries with the same order keep registration order.
+        self.items.sort_by_key(|item| item.order.unwrap_or(0));
+    }
+
+    /// Whether an entry already leads to `target`.
+    pub fn links_to(&self, target: &NavTarget) -> bool {
+        self.items.iter().any(|item| &item.target == target)
+    }
+
+    /// Entries to draw, in order.
+    pub fn visible(&self, private_unlocked: bool) -> impl Iterator<Item = &NavItem> {
+        self.items
+            .iter()
+            .filter(move |item| item.is_visible(private_unlocked))
+    }
+}
+
+fn section_page(section: NavSection) -> Page {
+    match section {
+        NavSection::Home => Page::Home,
+        NavSection::Projects => Page::Projects,
+        NavSection::Blog => Page::Blog,
+        NavSection::PrivateBlog => Page::PrivateBlog,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry(nav: &str) -> NavRegistry {
+        let source = format!(
+            "{}\n[site]\ntitle = \"T\"\ndescription = \"D\"\n[profile]\nname = \"N\"\n",
+            nav
+        );
+        NavRegistry::from_config(&site::parse(&source).unwrap())
+    }
+
+    fn page_item(label: &str, order: Option<i32>) -> NavItem {
+        NavItem {
+            label: label.to_string(),
+            target: NavTarget::Page(Page::Section(label.to_lowercase())),
+            visibility: NavVisibility::Always,
+            order,
+        }
+    }
+
+    fn labels<'a>(items: impl Iterator<Item = &'a NavItem>) -> Vec<&'a str> {
+        items.map(|item| item.label.as_str()).collect()
+    }
+
+    #[test]
+    fn entries_are_sorted_by_order_and_ties_keep_file_order() {
+        let nav = registry(
+            "nav = [{ label = \"Blog\", page = \"blog\", order = 2 },\
+                    { label = \"Home\", page = \"home\" },\
+                    { label = \"First\", url = \"https://a.example\", order = -1 },\
+                    { label = \"Projects\", page = \"projects\" }]",
+        );
+        assert_eq!(
+            labels(nav.visible(true)),
+            ["First", "Home", "Projects", "Blog"]
+        );
+    }
+
+    #[test]
+    fn registered_pages_join_in_by_their_order() {
+        let mut nav = registry(
+            "nav = [{ label = \"Home\", page = \"home\" },\
+                    { label = \"Blog\", page = \"blog\", order = 10 }]",
+        );
+        nav.register(page_item("About", Some(5)));
+        nav.register(page_item("Now", None));
+        nav.register(page_item("Uses", Some(10)));
+        assert_eq!(
+            labels(nav.visible(false)),
+            ["Home", "Now", "About", "Blog", "Uses"]
+        );
+        assert!(nav.links_to(&NavTarget::Page(Page::Section("about".to_string()))));
+        assert!(!nav.links_to(&NavTarget::Page(Page::Section("contact".to_string()))));
+    }
+
+    #[test]
+    fn hidden_and_unlocked_entries_are_filtered() {
+        let nav = registry(
+            "nav = [{ label = \"Home\", page = \"home\" },\
+                    { label = \"Secret\", page = \"blog\", visibility = \"hidden\" },\
+                    { label = \"Private\", page = \"private\", visibility = \"unlocked\" }]",
+        );
+        assert_eq!(labels(nav.visible(false)), ["Home"]);
+        assert_eq!(labels(nav.visible(true)), ["Home", "Private"]);
+    }
+
+    #[test]
+    fn external_entries_are_marked_and_link_out() {
+        let nav = registry("nav = [{ label = \"CV\", url = \"https://cv.example\" }, { label = \"Blog\", page = \"blog\" }]");
+        let items: Vec<&NavItem> = nav.visible(false).collect();
+        assert_eq!(items[0].display_label(), "CV ↗");
+        assert_eq!(items[0].href(), "https://cv.example");
+        assert!(!items[0].is_active(&Page::Blog));
+        assert_eq!(items[1].display_label(), "Blog");
+        assert_eq!(items[1].href(), "/blog");
+        assert!(items[1].is_active(&Page::BlogTag("rust".to_string())));
+    }
+}
@@ -0,0 +1,67 @@
+//! Standalone markdown pages, one file each in `pages/`.
+//!
+//! `pages/about.md` is served at `/about`, `pages/now.md` at `/now` and so
+//! on. Each file starts with a YAML frontmatter block; errors are reported the
+//! same way as for posts (see [`crate::frontmatter`]).
+//!
+//! This file is also compiled into `build.rs` so that malformed pages fail
+//! the build, which means it must only depend on `serde`, `serde_yaml`,
+//! `chrono` and `frontmatter.rs`.
+
+use crate::frontmatter::{self, Block, FrontmatterError, FrontmatterErrorKind};
+
+/// First path segments already taken by the built-in sections, which a page
+/// can't be named after.
+pub const RESERVED_SLUGS: &[&str] = &["projects", "blog", "private"];
+
+/// Paths under `/blog/` taken by the blog's own views (see
+/// `src/app/sections.rs`), which a post can't use as its slug.
+pub const RESERVED_POST_SLUGS: &[&str] = &["tag", "search", "stats"];
+
+/// Typed frontmatter of a page file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageMeta {
+    pub title: String,
+    /// Used for `<meta name="description">` on the pre-rendered page.
+    pub description: Option<String>,
+    /// Whether the page is listed in the top navigation.
+    pub nav: bool,
+    /// Position among the nav entries; see `[[nav]]` in `site.toml`.
+    pub nav_order: Option<i32>,
+}
+
+/// The frontmatter exactly as written in the file, before validation.
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPage {
+    title: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    nav: bool,
+    nav_order: Option<i32>,
+}
+
+/// Parse a page file into its frontmatter and markdown body.
+pub fn parse(file: &str, content: &str) -> Result<(PageMeta, String), FrontmatterError> {
+    let block = Block::split(file, content)?;
+    if block.is_empty() {
+        return Err(block.error(Some(2), FrontmatterErrorKind::MissingField("title")));
+    }
+    let raw: RawPage = block.deserialize()?;
+
+    let title = frontmatter::non_empty(raw.title).ok_or_else(|| {
+        block.error(
+            block.key_line("title"),
+            FrontmatterErrorKind::MissingField("title"),
+        )
+    })?;
+
+    let meta = PageMeta {
+        title,
+        description: frontmatter::non_empty(raw.description),
+        nav: raw.nav,
+        nav_order: raw.nav_order,
+    };
+
+    Ok((meta, block.body))
+}
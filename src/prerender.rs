@@ -0,0 +1,544 @@
+//! Static HTML versions of the public pages.
+//!
+//! The app draws everything into a single canvas, so crawlers, link previews
+//! and readers without JavaScript would otherwise only see an empty page.
+//! `src/bin/prerender.rs` renders each [`Page`] here into a copy of trunk's
+//! `index.html` at the page's own path; the WASM app removes the static
+//! content (`#prerendered`) once it starts. Private posts are never passed in.
+
+use std::fmt::Write as _;
+
+use crate::html::{escape, markdown_to_html};
+use crate::{search, site, BlogPost, MarkdownPage, MyApp, Page, Project};
+
+fn site_name() -> &'static str {
+    &site::embedded().title
+}
+
+/// Longest description we put in `<meta name="description">`.
+const DESCRIPTION_CHARS: usize = 160;
+
+/// One page, ready to be written into the template.
+pub struct StaticPage {
+    pub page: Page,
+    pub title: String,
+    pub description: String,
+    pub image: Option<String>,
+    /// Publication time for posts, as RFC 3339.
+    pub published: Option<String>,
+    /// The page content as HTML.
+    pub body: String,
+}
+
+impl StaticPage {
+    /// Where the page goes inside the dist directory, e.g. `blog/my-post/index.html`.
+    pub fn file_path(&self) -> String {
+        let route = self.page.to_url();
+        let route = route.trim_matches('/');
+        if route.is_empty() {
+            "index.html".to_string()
+        } else {
+            format!("{}/index.html", route)
+        }
+    }
+
+    /// How many directories deep [`Self::file_path`] is.
+    fn depth(&self) -> usize {
+        self.file_path().matches('/').count()
+    }
+}
+
+/// Every public page: Home, the project list and each project, the blog
+/// list, the tag pages and each post in `posts`.
+pub fn pages(
+    posts: &[BlogPost],
+    projects: &[Project],
+    markdown_pages: &[MarkdownPage],
+) -> Vec<StaticPage> {
+    let mut pages = vec![home(), project_list(projects), blog_list(posts)];
+
+    let mut tags: Vec<&String> = posts.iter().flat_map(|post| &post.meta.tags).collect();
+    tags.sort();
+    tags.dedup();
+    pages.push(tag_index(&tags));
+    for tag in tags {
+        let tagged: Vec<&BlogPost> = posts
+            .iter()
+            .filter(|post| post.meta.tags.contains(tag))
+            .collect();
+        pages.push(StaticPage {
+            page: Page::BlogTag(tag.clone()),
+            title: format!("#{} · {}", tag, site_name()),
+            description: format!("Blog posts tagged #{}.", tag),
+            image: None,
+            published: None,
+            body: format!("<h1>#{}</h1>\n{}", escape(tag), post_list(&tagged)),
+        });
+    }
+
+    pages.extend(projects.iter().map(project_page));
+    pages.extend(posts.iter().map(post_page));
+    pages.extend(markdown_pages.iter().map(markdown_page));
+    pages
+}
+
+fn home() -> StaticPage {
+    let profile = &site::embedded().profile;
+    let mut body = format!("<h1>{}</h1>\n", escape(&profile.name));
+    if let Some(tagline) = &profile.tagline {
+        let _ = writeln!(body, "<p>{}</p>", escape(tagline));
+    }
+    if let Some(bio) = &profile.bio {
+        body.push_str(&markdown_to_html(bio, ""));
+    }
+    body.push_str("<ul>\n");
+    for link in &profile.links {
+        let _ = writeln!(
+            body,
+            "<li><a href=\"{}\">{}</a></li>",
+            escape(&link.url),
+            escape(&link.label)
+        );
+    }
+    body.push_str("</ul>\n");
+
+    StaticPage {
+        page: Page::Home,
+        title: site_name().to_string(),
+        description: site::embedded().description.clone(),
+        image: None,
+        published: None,
+        body,
+    }
+}
+
+fn project_list(projects: &[Project]) -> StaticPage {
+    let mut body = String::from("<h1>Projects</h1>\n");
+    for project in projects {
+        let meta = &project.meta;
+        let _ = writeln!(
+            body,
+            "<h2><a href=\"{}\">{}</a></h2>",
+            escape(&Page::Project(project.slug.clone()).to_url()),
+            escape(&meta.name)
+        );
+        let _ = writeln!(body, "<p>{}</p>", escape(&meta.description));
+        if !meta.tech.is_empty() {
+            let _ = writeln!(body, "<p>{}</p>", escape(&meta.tech.join(", ")));
+        }
+    }
+
+    StaticPage {
+        page: Page::Projects,
+        title: format!("Projects · {}", site_name()),
+        description: projects
+            .iter()
+            .map(|project| project.meta.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+        image: None,
+        published: None,
+        body,
+    }
+}
+
+fn project_page(project: &Project) -> StaticPage {
+    let meta = &project.meta;
+    let mut body = format!(
+        "<article>\n<h1>{}</h1>\n<p>{}</p>\n",
+        escape(&meta.name),
+        escape(&meta.description)
+    );
+    let links = [
+        ("Website", &meta.url),
+        ("Source code", &meta.repo),
+        ("Live demo", &meta.demo),
+    ];
+    for (label, url) in links {
+        if let Some(url) = url {
+            let _ = writeln!(body, "<p><a href=\"{}\">{}</a></p>", escape(url), label);
+        }
+    }
+    if !meta.tech.is_empty() {
+        body.push_str("<h2>Tech stack</h2>\n<ul>\n");
+        for tech in &meta.tech {
+            let _ = writeln!(body, "<li>{}</li>", escape(tech));
+        }
+        body.push_str("</ul>\n");
+    }
+    body.push_str(&markdown_to_html(&project.content, ""));
+    body.push_str("</article>\n");
+
+    StaticPage {
+        page: Page::Project(project.slug.clone()),
+        title: format!("{} · {}", meta.name, site_name()),
+        description: meta.description.clone(),
+        image: None,
+        published: None,
+        body,
+    }
+}
+
+fn blog_list(posts: &[BlogPost]) -> StaticPage {
+    StaticPage {
+        page: Page::Blog,
+        title: format!("Blog · {}", site_name()),
+        description: format!("Blog posts by {}.", site::embedded().author),
+        image: None,
+        published: None,
+        body: format!(
+            "<h1>Blog</h1>\n{}",
+            post_list(&posts.iter().collect::<Vec<_>>())
+        ),
+    }
+}
+
+fn tag_index(tags: &[&String]) -> StaticPage {
+    let mut body = String::from("<h1>Tags</h1>\n<ul>\n");
+    for tag in tags {
+        let _ = writeln!(
+            body,
+            "<li><a href=\"{}\">#{}</a></li>",
+            escape(&Page::BlogTag(tag.to_string()).to_url()),
+            escape(tag)
+        );
+    }
+    body.push_str("</ul>\n");
+
+    StaticPage {
+        page: Page::BlogTags,
+        title: format!("Tags · {}", site_name()),
+        description: format!("Every tag used on {}'s blog.", site::embedded().author),
+        image: None,
+        published: None,
+        body,
+    }
+}
+
+fn post_list(posts: &[&BlogPost]) -> String {
+    let mut html = String::from("<ul>\n");
+    for post in posts {
+        let _ = write!(
+            html,
+            "<li><a href=\"{}\">{}</a> <time datetime=\"{}\">{}</time>",
+            escape(&Page::BlogPost(post.slug.clone()).to_url()),
+            escape(&post.title),
+            post.published_at.to_rfc3339(),
+            escape(&post.date_display)
+        );
+        if let Some(summary) = &post.meta.summary {
+            let _ = write!(html, "<p>{}</p>", escape(summary));
+        }
+        html.push_str("</li>\n");
+    }
+    html.push_str("</ul>\n");
+    html
+}
+
+fn post_page(post: &BlogPost) -> StaticPage {
+    let description = post.meta.summary.clone().unwrap_or_else(|| {
+        search::snippet(&post.content, "", DESCRIPTION_CHARS)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect()
+    });
+
+    let mut body = format!(
+        "<article>\n<h1>{}</h1>\n<p><time datetime=\"{}\">{}</time> · {}</p>\n",
+        escape(&post.title),
+        post.published_at.to_rfc3339(),
+        escape(&post.date_display),
+        post.stats.reading_time()
+    );
+    body.push_str(&markdown_to_html(&post.content, ""));
+    body.push_str("</article>\n");
+
+    StaticPage {
+        page: Page::BlogPost(post.slug.clone()),
+        title: format!("{} · {}", post.title, site_name()),
+        description,
+        image: post.meta.cover_image.clone(),
+        published: Some(post.published_at.to_rfc3339()),
+        body,
+    }
+}
+
+fn markdown_page(page: &MarkdownPage) -> StaticPage {
+    let description = page.meta.description.clone().unwrap_or_else(|| {
+        search::snippet(&page.content, "", DESCRIPTION_CHARS)
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect()
+    });
+
+    let mut body = format!("<article>\n<h1>{}</h1>\n", escape(&page.meta.title));
+    body.push_str(&markdown_to_html(&page.content, ""));
+    body.push_str("</article>\n");
+
+    StaticPage {
+        page: Page::Section(page.slug.clone()),
+        title: format!("{} · {}", page.meta.title, site_name()),
+        description,
+        image: None,
+        published: None,
+        body,
+    }
+}
+
+/// Fill trunk's `index.html` with `page`. `site_url` (absolute, no trailing
+/// slash) is needed for `og:url`, the canonical link, absolute image links
+/// and the feed links; without it those are left out or kept relative.
+pub fn render(template: &str, page: &StaticPage, site_url: Option<&str>) -> String {
+    let mut head = format!("<title>{}</title>\n", escape(&page.title));
+    let mut meta = |property: &str, name: &str, content: &str| {
+        let _ = writeln!(
+            head,
+            "    <meta {}=\"{}\" content=\"{}\">",
+            property,
+            name,
+            escape(content)
+        );
+    };
+    meta("name", "description", &page.description);
+    meta("property", "og:site_name", site_name());
+    meta("property", "og:title", &page.title);
+    meta("property", "og:description", &page.description);
+    match &page.published {
+        Some(published) => {
+            meta("property", "og:type", "article");
+            meta("property", "article:published_time", published);
+        }
+        None => meta("property", "og:type", "website"),
+    }
+    if let Some(site_url) = site_url {
+        meta(
+            "property",
+            "og:url",
+            &format!("{}{}", site_url, page.page.to_url()),
+        );
+    }
+    if let Some(image) = &page.image {
+        let image = match site_url {
+            Some(site_url) if image.starts_with('/') => format!("{}{}", site_url, image),
+            _ => image.clone(),
+        };
+        meta("property", "og:image", &image);
+        meta("name", "twitter:card", "summary_large_image");
+    } else {
+        meta("name", "twitter:card", "summary");
+    }
+    for (name, content) in &site::embedded().meta {
+        if name.starts_with("og:") {
+            meta("property", name, content);
+        } else {
+            meta("name", name, content);
+        }
+    }
+
+    if let Some(site_url) = site_url {
+        let _ = writeln!(
+            head,
+            "    <link rel=\"canonical\" href=\"{}\">",
+            escape(&format!("{}{}", site_url, page.page.to_url()))
+        );
+    }
+
+    // `src/bin/feeds.rs` only writes the feeds when the site URL is known.
+    let feeds = if site_url.is_some() {
+        [("rss", "rss.xml"), ("atom", "atom.xml")].as_slice()
+    } else {
+        &[]
+    };
+    for (kind, file_name) in feeds {
+        let _ = writeln!(
+            head,
+            "    <link rel=\"alternate\" type=\"application/{}+xml\" title=\"{}\" href=\"{}{}\">",
+            kind,
+            escape(site_name()),
+            root_prefix(page.depth()),
+            file_name
+        );
+    }
+
+    // Only the template's own URLs are relocated, never the page content.
+    let template = relocate(template, page.depth());
+    let mut html = replace_title(&template, head.trim_end());
+
+    let nav: Vec<String> = MyApp::nav_registry()
+        .visible(false)
+        .map(|item| {
+            format!(
+                "<a href=\"{}\">{}</a>",
+                escape(&item.href()),
+                escape(&item.label)
+            )
+        })
+        .collect();
+    let footer = site::embedded()
+        .footer
+        .as_ref()
+        .map(|footer| format!("<footer><p>{}</p></footer>\n", escape(footer)))
+        .unwrap_or_default();
+    let content = format!(
+        "<noscript><style>canvas {{ display: none; }} html, body {{ overflow: auto !important; background: white; }}</style></noscript>\n\
+         <main id=\"prerendered\" style=\"max-width: 720px; margin: 0 auto; padding: 24px; font-family: sans-serif;\">\n\
+         <nav>{}</nav>\n\
+         {}{}</main>\n",
+        nav.join(" · "),
+        page.body,
+        footer
+    );
+    if let Some(index) = html.find("<body>") {
+        html.insert_str(index + "<body>".len(), &format!("\n{}", content));
+    }
+    html
+}
+
+/// `404.html` for hosts without rewrites, such as GitHub Pages, in hash
+/// routing mode: it sends `/blog/my-post` on to `/#/blog/my-post`, where the
+/// app draws the page, or its own "not found" page. The site must be served
+/// from the root of its domain.
+pub fn not_found_redirect() -> String {
+    format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>Page not found · {}</title>\n\
+         <script>location.replace(\"/#\" + location.pathname + location.search + location.hash);</script>\n\
+         </head>\n\
+         <body>\n\
+         <p>Page not found. <a href=\"/\">Go to the home page</a>.</p>\n\
+         </body>\n\
+         </html>\n",
+        escape(site_name())
+    )
+}
+
+fn replace_title(template: &str, head: &str) -> String {
+    match (template.find("<title>"), template.find("</title>")) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}{}{}",
+            &template[..start],
+            head,
+            &template[end + "</title>".len()..]
+        ),
+        _ => template.replacen("</head>", &format!("{}\n</head>", head), 1),
+    }
+}
+
+/// The site root seen from a page `depth` directories down: `./`, `../`, ...
+fn root_prefix(depth: usize) -> String {
+    if depth == 0 {
+        "./".to_string()
+    } else {
+        "../".repeat(depth)
+    }
+}
+
+/// Point the `./`-relative asset URLs trunk emits with `--public-url .` back
+/// at the site root from a page `depth` directories down.
+fn relocate(template: &str, depth: usize) -> String {
+    if depth == 0 {
+        return template.to_string();
+    }
+    let prefix = root_prefix(depth);
+    template
+        .replace("\"./", &format!("\"{}", prefix))
+        .replace("'./", &format!("'{}", prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "<html><head>\n<title></title>\n\
+        <link rel=\"modulepreload\" href=\"./ps.js\">\n</head><body>\n\
+        <script type=\"module\">import init from './ps.js';</script>\n</body></html>";
+
+    fn static_page(page: Page, body: &str) -> StaticPage {
+        StaticPage {
+            page,
+            title: "A \"quoted\" <title>".to_string(),
+            description: "Tips & tricks".to_string(),
+            image: None,
+            published: None,
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn pages_are_written_at_their_route() {
+        let path = |page| static_page(page, "").file_path();
+        assert_eq!(path(Page::Home), "index.html");
+        assert_eq!(path(Page::Projects), "projects/index.html");
+        assert_eq!(path(Page::Blog), "blog/index.html");
+        assert_eq!(
+            path(Page::BlogPost("my-post".to_string())),
+            "blog/my-post/index.html"
+        );
+        assert_eq!(
+            path(Page::BlogTag("rust".to_string())),
+            "blog/tag/rust/index.html"
+        );
+        assert_eq!(static_page(Page::Blog, "").depth(), 1);
+        assert_eq!(static_page(Page::BlogPost("a".to_string()), "").depth(), 2);
+    }
+
+    #[test]
+    fn relocate_points_relative_urls_at_the_site_root() {
+        let html = "<link href=\"./ps.js\"><script>import('./ps_bg.wasm')</script>";
+        assert_eq!(relocate(html, 0), html);
+        assert_eq!(
+            relocate(html, 2),
+            "<link href=\"../../ps.js\"><script>import('../../ps_bg.wasm')</script>"
+        );
+        assert_eq!(relocate("<a href=\"/blog\">", 1), "<a href=\"/blog\">");
+    }
+
+    #[test]
+    fn render_fills_in_the_head() {
+        let page = static_page(Page::BlogPost("my-post".to_string()), "<p>Hi</p>");
+        let html = render(TEMPLATE, &page, Some("https://example.com"));
+        assert!(html.contains("<title>A &quot;quoted&quot; &lt;title&gt;</title>"));
+        assert!(html.contains("<meta name=\"description\" content=\"Tips &amp; tricks\">"));
+        assert!(html.contains("<link rel=\"canonical\" href=\"https://example.com/blog/my-post\">"));
+        assert!(html
+            .contains("<meta property=\"og:url\" content=\"https://example.com/blog/my-post\">"));
+        assert!(html.contains("type=\"application/rss+xml\""));
+        assert!(html.contains("href=\"../../rss.xml\""));
+        assert!(html.contains("href=\"../../atom.xml\""));
+        assert!(html.contains("<p>Hi</p>"));
+        assert_eq!(html.matches("<title>").count(), 1);
+    }
+
+    #[test]
+    fn render_without_a_site_url_leaves_out_absolute_links() {
+        let html = render(TEMPLATE, &static_page(Page::Blog, ""), None);
+        assert!(!html.contains("og:url"));
+        assert!(!html.contains("rel=\"canonical\""));
+        assert!(!html.contains("rss.xml"));
+    }
+
+    #[test]
+    fn render_relocates_the_template_but_not_the_content() {
+        let body = "<pre><code>cd \"./src\" &amp;&amp; ls './dist'</code></pre>";
+        let page = static_page(Page::BlogPost("my-post".to_string()), body);
+        let html = render(TEMPLATE, &page, None);
+        assert!(html.contains(body));
+        assert!(html.contains("<link rel=\"modulepreload\" href=\"../../ps.js\">"));
+        assert!(html.contains("import init from '../../ps.js';"));
+    }
+
+    #[test]
+    fn not_found_redirect_moves_the_path_into_the_fragment() {
+        let html = not_found_redirect();
+        assert!(html.contains(&format!(
+            "<title>Page not found · {}</title>",
+            escape(site_name())
+        )));
+        assert!(html.contains(
+            "location.replace(\"/#\" + location.pathname + location.search + location.hash)"
+        ));
+    }
+}
@@ -0,0 +1,427 @@
+//! Projects, one markdown file each in `projects/`.
+//!
+//! Every file starts with a YAML frontmatter block describing the project;
+//! anything after it is a longer write-up shown on the project's own page
+//! (`/projects/{slug}`, where the slug is the file name). Errors are reported the same way
+//! as for posts (see [`crate::frontmatter`]).
+//!
+//! This file is also compiled into `build.rs` so that malformed projects fail
+//! the build, which means it must only depend on `serde`, `serde_yaml`,
+//! `chrono` and `frontmatter.rs`.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::frontmatter::{self, Block, FrontmatterError, FrontmatterErrorKind};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectStatus {
+    /// Being worked on.
+    Active,
+    /// Done, but still kept running or up to date.
+    Maintained,
+    Finished,
+    /// No longer worked on or available.
+    Archived,
+    /// Stopped before it was finished.
+    Abandoned,
+}
+
+impl ProjectStatus {
+    pub const ALL: [ProjectStatus; 5] = [
+        ProjectStatus::Active,
+        ProjectStatus::Maintained,
+        ProjectStatus::Finished,
+        ProjectStatus::Archived,
+        ProjectStatus::Abandoned,
+    ];
+
+    /// The lowercase name used in frontmatter and URLs.
+    pub fn slug(self) -> &'static str {
+        match self {
+            ProjectStatus::Active => "active",
+            ProjectStatus::Maintained => "maintained",
+            ProjectStatus::Finished => "finished",
+            ProjectStatus::Archived => "archived",
+            ProjectStatus::Abandoned => "abandoned",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.slug() == slug)
+    }
+}
+
+impl fmt::Display for ProjectStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProjectStatus::Active => "Active",
+            ProjectStatus::Maintained => "Maintained",
+            ProjectStatus::Finished => "Finished",
+            ProjectStatus::Archived => "Archived",
+            ProjectStatus::Abandoned => "Abandoned",
+        })
+    }
+}
+
+/// How the projects page orders its list.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum ProjectSort {
+    /// [`ProjectMeta::display_order`].
+    #[default]
+    Featured,
+    /// Most recently started first.
+    Newest,
+    /// By name, A to Z.
+    Alphabetical,
+}
+
+impl ProjectSort {
+    pub const ALL: [ProjectSort; 3] = [
+        ProjectSort::Featured,
+        ProjectSort::Newest,
+        ProjectSort::Alphabetical,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProjectSort::Featured => "Featured",
+            ProjectSort::Newest => "Newest",
+            ProjectSort::Alphabetical => "A–Z",
+        }
+    }
+
+    /// The value of the `sort` query parameter.
+    pub fn slug(self) -> &'static str {
+        match self {
+            ProjectSort::Featured => "featured",
+            ProjectSort::Newest => "newest",
+            ProjectSort::Alphabetical => "name",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|sort| sort.slug() == slug)
+    }
+
+    pub fn compare(self, a: &ProjectMeta, b: &ProjectMeta) -> Ordering {
+        match self {
+            ProjectSort::Featured => a.display_order(b),
+            ProjectSort::Newest => match (&a.started, &b.started) {
+                (Some(a), Some(b)) => b.cmp(a),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+            .then_with(|| a.display_order(b)),
+            ProjectSort::Alphabetical => a
+                .name
+                .to_lowercase()
+                .cmp(&b.name.to_lowercase())
+                .then_with(|| a.name.cmp(&b.name)),
+        }
+    }
+}
+
+/// Typed frontmatter of a project file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectMeta {
+    pub name: String,
+    /// The project's own website.
+    pub url: Option<String>,
+    /// Source code.
+    pub repo: Option<String>,
+    /// A running instance to try out.
+    pub demo: Option<String>,
+    /// One line shown under the name.
+    pub description: String,
+    /// Languages, frameworks and services, in the order written.
+    pub tech: Vec<String>,
+    pub status: Option<ProjectStatus>,
+    pub started: Option<DateTime<FixedOffset>>,
+    pub ended: Option<DateTime<FixedOffset>>,
+    /// Explicit position on the projects page; lower comes first and
+    /// projects without one follow, newest first.
+    pub order: Option<i32>,
+}
+
+impl ProjectMeta {
+    /// Whether `tech` (compared as a tag slug, e.g. `google-bigquery`) is in
+    /// the tech stack.
+    pub fn uses(&self, tech: &str) -> bool {
+        self.tech
+            .iter()
+            .any(|item| frontmatter::tag_slug(item) == tech)
+    }
+
+    /// The order projects are listed in by default.
+    pub fn display_order(&self, other: &Self) -> Ordering {
+        match (self.order, other.order) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+        .then_with(|| other.started.cmp(&self.started))
+        .then_with(|| self.name.cmp(&other.name))
+    }
+
+    /// "Mar 2023 – Present", "2021 – 2022" style date range, if any dates are set.
+    pub fn date_range(&self) -> Option<String> {
+        let format = |date: &DateTime<FixedOffset>| date.format("%b %Y").to_string();
+        match (&self.started, &self.ended) {
+            (Some(started), Some(ended)) => {
+                Some(format!("{} – {}", format(started), format(ended)))
+            }
+            (Some(started), None) if self.status == Some(ProjectStatus::Active) => {
+                Some(format!("{} – Present", format(started)))
+            }
+            (Some(started), None) => Some(format(started)),
+            (None, Some(ended)) => Some(format(ended)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// The frontmatter exactly as written in the file, before validation.
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawProject {
+    name: Option<String>,
+    url: Option<String>,
+    repo: Option<String>,
+    demo: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    tech: Vec<String>,
+    status: Option<ProjectStatus>,
+    started: Option<String>,
+    ended: Option<String>,
+    order: Option<i32>,
+}
+
+/// Parse a project file into its frontmatter and markdown write-up.
+pub fn parse(file: &str, content: &str) -> Result<(ProjectMeta, String), FrontmatterError> {
+    let block = Block::split(file, content)?;
+    if block.is_empty() {
+        return Err(block.error(Some(2), FrontmatterErrorKind::MissingField("name")));
+    }
+    let raw: RawProject = block.deserialize()?;
+
+    let required = |value: Option<String>, field: &'static str| {
+        frontmatter::non_empty(value).ok_or_else(|| {
+            block.error(
+                block.key_line(field),
+                FrontmatterErrorKind::MissingField(field),
+            )
+        })
+    };
+    let name = required(raw.name, "name")?;
+    let description = required(raw.description, "description")?;
+
+    let started = match raw.started {
+        Some(value) => Some(block.date("started", &value)?),
+        None => None,
+    };
+    let ended = match raw.ended {
+        Some(value) => Some(block.date("ended", &value)?),
+        None => None,
+    };
+
+    let mut tech: Vec<String> = Vec::new();
+    for item in raw.tech {
+        let item = item.trim().to_string();
+        if !item.is_empty() && !tech.contains(&item) {
+            tech.push(item);
+        }
+    }
+
+    let meta = ProjectMeta {
+        name,
+        url: frontmatter::non_empty(raw.url),
+        repo: frontmatter::non_empty(raw.repo),
+        demo: frontmatter::non_empty(raw.demo),
+        description,
+        tech,
+        status: raw.status,
+        started,
+        ended,
+        order: raw.order,
+    };
+
+    Ok((meta, block.body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(frontmatter: &str) -> ProjectMeta {
+        parse("p.md", &format!("---\n{}\n---\n", frontmatter))
+            .unwrap()
+            .0
+    }
+
+    fn project(name: &str, order: Option<i32>, started: Option<&str>) -> ProjectMeta {
+        ProjectMeta {
+            order,
+            started: started.and_then(frontmatter::parse_date),
+            ..meta(&format!("name: {}\ndescription: d", name))
+        }
+    }
+
+    fn error(content: &str) -> (Option<usize>, FrontmatterErrorKind) {
+        let err = parse("p.md", content).unwrap_err();
+        assert_eq!(err.file, "p.md");
+        (err.line, err.kind)
+    }
+
+    #[test]
+    fn parses_every_field() {
+        let (meta, body) = parse(
+            "p.md",
+            "---\nname: \" Zeitgus \"\nurl: https://z.example\nrepo: \" \"\ndemo: https://d.example\n\
+             description: A clock\ntech: [Rust, \" egui \", Rust, \"\"]\nstatus: maintained\n\
+             started: 2023-03-01\nended: 2024-01-15\norder: 2\n---\nWrite-up\n",
+        )
+        .unwrap();
+        assert_eq!(meta.name, "Zeitgus");
+        assert_eq!(meta.url.as_deref(), Some("https://z.example"));
+        assert_eq!(meta.repo, None);
+        assert_eq!(meta.demo.as_deref(), Some("https://d.example"));
+        assert_eq!(meta.description, "A clock");
+        assert_eq!(meta.tech, ["Rust", "egui"]);
+        assert_eq!(meta.status, Some(ProjectStatus::Maintained));
+        assert_eq!(meta.started, frontmatter::parse_date("2023-03-01"));
+        assert_eq!(meta.ended, frontmatter::parse_date("2024-01-15"));
+        assert_eq!(meta.order, Some(2));
+        assert_eq!(body, "Write-up\n");
+    }
+
+    #[test]
+    fn reports_missing_name_and_description() {
+        assert_eq!(
+            error("---\n---\n"),
+            (Some(2), FrontmatterErrorKind::MissingField("name"))
+        );
+        assert_eq!(
+            error("---\ndescription: d\n---\n"),
+            (None, FrontmatterErrorKind::MissingField("name"))
+        );
+        assert_eq!(
+            error("---\nname: n\ndescription: \"\"\n---\n"),
+            (Some(3), FrontmatterErrorKind::MissingField("description"))
+        );
+    }
+
+    #[test]
+    fn reports_unknown_statuses_and_bad_dates_on_their_line() {
+        let (line, kind) = error("---\nname: n\ndescription: d\nstatus: paused\n---\n");
+        assert_eq!(line, Some(4));
+        assert!(
+            matches!(&kind, FrontmatterErrorKind::Yaml(message) if message.contains("paused")),
+            "{:?}",
+            kind
+        );
+        assert_eq!(
+            error("---\nname: n\ndescription: d\nstarted: someday\n---\n"),
+            (
+                Some(4),
+                FrontmatterErrorKind::InvalidDate {
+                    field: "started",
+                    value: "someday".to_string(),
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn uses_compares_tech_as_tag_slugs() {
+        let meta = meta("name: n\ndescription: d\ntech: [Google BigQuery]");
+        assert!(meta.uses("google-bigquery"));
+        assert!(!meta.uses("google"));
+    }
+
+    #[test]
+    fn statuses_and_sorts_round_trip_through_their_slugs() {
+        for status in ProjectStatus::ALL {
+            assert_eq!(ProjectStatus::from_slug(status.slug()), Some(status));
+        }
+        for sort in ProjectSort::ALL {
+            assert_eq!(ProjectSort::from_slug(sort.slug()), Some(sort));
+        }
+        assert_eq!(ProjectStatus::from_slug("Active"), None);
+        assert_eq!(ProjectSort::from_slug("oldest"), None);
+    }
+
+    fn sorted(sort: ProjectSort, mut projects: Vec<ProjectMeta>) -> Vec<String> {
+        projects.sort_by(|a, b| sort.compare(a, b));
+        projects.into_iter().map(|project| project.name).collect()
+    }
+
+    #[test]
+    fn featured_puts_explicit_order_first_then_newest_then_name() {
+        let projects = vec![
+            project("Old", None, Some("2020-01-01")),
+            project("Second", Some(2), None),
+            project("New", None, Some("2024-01-01")),
+            project("Undated B", None, None),
+            project("First", Some(1), Some("2019-01-01")),
+            project("Undated A", None, None),
+        ];
+        assert_eq!(
+            sorted(ProjectSort::Featured, projects),
+            ["First", "Second", "New", "Old", "Undated A", "Undated B"]
+        );
+    }
+
+    #[test]
+    fn newest_puts_undated_projects_last() {
+        let projects = vec![
+            project("Undated", Some(1), None),
+            project("Old", None, Some("2020-01-01")),
+            project("New", None, Some("2024-01-01")),
+        ];
+        assert_eq!(
+            sorted(ProjectSort::Newest, projects),
+            ["New", "Old", "Undated"]
+        );
+    }
+
+    #[test]
+    fn alphabetical_ignores_case() {
+        let projects = vec![
+            project("beta", None, None),
+            project("Alpha", None, None),
+            project("alpha", None, None),
+        ];
+        assert_eq!(
+            sorted(ProjectSort::Alphabetical, projects),
+            ["Alpha", "alpha", "beta"]
+        );
+    }
+
+    #[test]
+    fn date_ranges() {
+        let range = |frontmatter: &str| {
+            meta(&format!("name: n\ndescription: d\n{}", frontmatter)).date_range()
+        };
+        assert_eq!(range(""), None);
+        assert_eq!(
+            range("started: 2021-03-01\nended: 2022-11-30"),
+            Some("Mar 2021 – Nov 2022".to_string())
+        );
+        assert_eq!(
+            range("started: 2023-03-01\nstatus: active"),
+            Some("Mar 2023 – Present".to_string())
+        );
+        assert_eq!(
+            range("started: 2023-03-01\nstatus: finished"),
+            Some("Mar 2023".to_string())
+        );
+        assert_eq!(range("ended: 2022-11-30"), Some("Nov 2022".to_string()));
+    }
+}
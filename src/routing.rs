@@ -0,0 +1,116 @@
+//! Where in the URL the web app keeps the current page.
+//!
+//! Pages are always written as paths (`/blog/my-post`, see `Page::to_url`);
+//! the routing mode only decides whether that path is the real URL path or
+//! sits in the fragment (`/#/blog/my-post`). It is chosen at build time with
+//! `SITE_ROUTING`, since it depends on where the site is hosted.
+//!
+//! This file is also compiled into `build.rs` so that an unknown mode fails
+//! the build, which means it must not depend on the rest of the crate.
+
+/// The build-time environment variable that selects the mode.
+pub const ROUTING_VAR: &str = "SITE_ROUTING";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RoutingMode {
+    /// `/blog/my-post`. The host must serve `index.html` for every path, as
+    /// the rewrite in `vercel.json` does.
+    #[default]
+    Path,
+    /// `/#/blog/my-post`. Works on any static host, e.g. GitHub Pages, which
+    /// can't rewrite paths.
+    Hash,
+}
+
+impl RoutingMode {
+    pub const ALL: [RoutingMode; 2] = [RoutingMode::Path, RoutingMode::Hash];
+
+    /// The mode this binary was built with.
+    pub fn embedded() -> Self {
+        // `build.rs` rejects unknown values.
+        option_env!("SITE_ROUTING")
+            .and_then(Self::from_name)
+            .unwrap_or_default()
+    }
+
+    /// The mode for a `SITE_ROUTING` value; empty means the default.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "" => Some(Self::default()),
+            name => Self::ALL.into_iter().find(|mode| mode.name() == name),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RoutingMode::Path => "path",
+            RoutingMode::Hash => "hash",
+        }
+    }
+
+    /// What to put in the address bar for `route`, a path from
+    /// `Page::to_url`.
+    pub fn href(self, route: &str) -> String {
+        match self {
+            RoutingMode::Path => route.to_string(),
+            RoutingMode::Hash => format!("#{}", route),
+        }
+    }
+
+    /// The route in a URL made of `path`, `query` (with its `?`) and
+    /// `fragment` (with its `#`), as `window.location` gives them. In hash
+    /// mode that is everything after the first `#`, which may hold a second
+    /// one for a heading (`#/blog/my-post#why-rust`).
+    ///
+    /// In hash mode a URL without a `#/` route, such as a link to one of the
+    /// pre-rendered pages, falls back to its path.
+    pub fn route(self, path: &str, query: &str, fragment: &str) -> String {
+        match self {
+            RoutingMode::Hash if fragment.starts_with("#/") => fragment[1..].to_string(),
+            _ => format!("{}{}{}", path, query, fragment),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for mode in RoutingMode::ALL {
+            assert_eq!(RoutingMode::from_name(mode.name()), Some(mode));
+        }
+        assert_eq!(RoutingMode::from_name(" "), Some(RoutingMode::Path));
+        assert_eq!(RoutingMode::from_name(" hash\n"), Some(RoutingMode::Hash));
+        assert_eq!(RoutingMode::from_name("history"), None);
+    }
+
+    #[test]
+    fn path_mode_uses_the_whole_url() {
+        let mode = RoutingMode::Path;
+        assert_eq!(mode.href("/blog/post?a=1#why"), "/blog/post?a=1#why");
+        assert_eq!(
+            mode.route("/blog/post", "?a=1", "#why"),
+            "/blog/post?a=1#why"
+        );
+    }
+
+    #[test]
+    fn hash_mode_uses_the_fragment() {
+        let mode = RoutingMode::Hash;
+        assert_eq!(mode.href("/blog/post?a=1#why"), "#/blog/post?a=1#why");
+        assert_eq!(
+            mode.route("/", "", "#/blog/post?a=1#why"),
+            "/blog/post?a=1#why"
+        );
+        assert_eq!(mode.route("/site/", "?x=1", "#/"), "/");
+    }
+
+    #[test]
+    fn hash_mode_falls_back_to_the_path() {
+        let mode = RoutingMode::Hash;
+        assert_eq!(mode.route("/blog/post", "", ""), "/blog/post");
+        assert_eq!(mode.route("/blog/post", "", "#why"), "/blog/post#why");
+    }
+}
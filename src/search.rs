@@ -0,0 +1,389 @@
+//! In-memory full-text search over blog posts.
+//!
+//! The index is built once at startup from the embedded posts, so searching
+//! works offline and needs no server. Ranking is BM25 over the post body with
+//! title matches weighted more heavily; the last query term is matched as a
+//! prefix so results update sensibly while the user is still typing. The
+//! same index finds posts similar to a given one.
+
+use std::collections::BTreeMap;
+
+/// BM25 term-frequency saturation.
+const K1: f32 = 1.2;
+/// BM25 document-length normalisation.
+const B: f32 = 0.75;
+/// A title hit counts as this many body hits.
+const TITLE_WEIGHT: f32 = 3.0;
+/// Prefix matches score a little below exact ones.
+const PREFIX_WEIGHT: f32 = 0.7;
+
+#[derive(Clone, Copy, Debug)]
+struct Posting {
+    doc: usize,
+    title_hits: u32,
+    body_hits: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchHit {
+    /// Index of the document in the order it was passed to [`SearchIndex::new`].
+    pub doc: usize,
+    pub score: f32,
+}
+
+/// An inverted index from lowercase terms to the documents containing them.
+#[derive(Default)]
+pub struct SearchIndex {
+    postings: BTreeMap<String, Vec<Posting>>,
+    doc_lengths: Vec<usize>,
+    average_length: f32,
+}
+
+impl SearchIndex {
+    /// Index `(title, markdown body)` pairs.
+    pub fn new<'a>(docs: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut postings: BTreeMap<String, Vec<Posting>> = BTreeMap::new();
+        let mut doc_lengths = Vec::new();
+
+        for (doc, (title, body)) in docs.into_iter().enumerate() {
+            let mut counts: BTreeMap<String, (u32, u32)> = BTreeMap::new();
+            for term in tokenize(title) {
+                counts.entry(term).or_default().0 += 1;
+            }
+            let mut length = 0;
+            for term in tokenize(body) {
+                counts.entry(term).or_default().1 += 1;
+                length += 1;
+            }
+            doc_lengths.push(length);
+
+            for (term, (title_hits, body_hits)) in counts {
+                postings.entry(term).or_default().push(Posting {
+                    doc,
+                    title_hits,
+                    body_hits,
+                });
+            }
+        }
+
+        let average_length = if doc_lengths.is_empty() {
+            0.0
+        } else {
+            doc_lengths.iter().sum::<usize>() as f32 / doc_lengths.len() as f32
+        };
+
+        Self {
+            postings,
+            doc_lengths,
+            average_length,
+        }
+    }
+
+    /// Documents containing every query term, best match first.
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let terms: Vec<String> = tokenize(query).collect();
+        let Some((last, rest)) = terms.split_last() else {
+            return Vec::new();
+        };
+
+        let mut scores: Option<BTreeMap<usize, f32>> = None;
+        for (index, term) in rest.iter().chain(std::iter::once(last)).enumerate() {
+            let is_last = index == rest.len();
+            let mut term_scores: BTreeMap<usize, f32> = BTreeMap::new();
+
+            let matches: Vec<(&String, &Vec<Posting>)> = if is_last {
+                self.postings
+                    .range(term.clone()..)
+                    .take_while(|(candidate, _)| candidate.starts_with(term.as_str()))
+                    .collect()
+            } else {
+                self.postings.get_key_value(term).into_iter().collect()
+            };
+
+            for (candidate, postings) in matches {
+                let weight = if candidate == term {
+                    1.0
+                } else {
+                    PREFIX_WEIGHT
+                };
+                for posting in postings {
+                    *term_scores.entry(posting.doc).or_default() +=
+                        weight * self.bm25(posting, postings.len());
+                }
+            }
+
+            // Intersect with the documents matched by the previous terms.
+            scores = Some(match scores {
+                None => term_scores,
+                Some(previous) => previous
+                    .into_iter()
+                    .filter_map(|(doc, score)| term_scores.get(&doc).map(|s| (doc, score + s)))
+                    .collect(),
+            });
+        }
+
+        let mut hits: Vec<SearchHit> = scores
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(doc, score)| SearchHit { doc, score })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.doc.cmp(&b.doc)));
+        hits
+    }
+
+    /// The other documents sharing distinctive terms with `doc`, most similar
+    /// first. Scores are the cosine similarity of the documents' TF-IDF
+    /// vectors, from 0 to 1, so long documents don't resemble everything;
+    /// terms found in every document count for nothing.
+    pub fn similar(&self, doc: usize) -> Vec<SearchHit> {
+        let docs = self.doc_lengths.len() as f32;
+        let mut norms = vec![0.0_f32; self.doc_lengths.len()];
+        let mut dot_products: BTreeMap<usize, f32> = BTreeMap::new();
+
+        for postings in self.postings.values() {
+            let idf = (docs / postings.len() as f32).ln();
+            if idf <= 0.0 {
+                continue;
+            }
+            let weight = |posting: &Posting| {
+                let tf = posting.body_hits as f32 + TITLE_WEIGHT * posting.title_hits as f32;
+                (1.0 + tf.ln()) * idf
+            };
+
+            let own = postings
+                .iter()
+                .find(|posting| posting.doc == doc)
+                .map(weight);
+            for posting in postings {
+                let other = weight(posting);
+                norms[posting.doc] += other * other;
+                if let Some(own) = own.filter(|_| posting.doc != doc) {
+                    *dot_products.entry(posting.doc).or_default() += own * other;
+                }
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = dot_products
+            .into_iter()
+            .filter_map(|(other, dot_product)| {
+                let norm = (norms[doc] * norms[other]).sqrt();
+                (norm > 0.0).then(|| SearchHit {
+                    doc: other,
+                    score: dot_product / norm,
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.doc.cmp(&b.doc)));
+        hits
+    }
+
+    fn bm25(&self, posting: &Posting, document_frequency: usize) -> f32 {
+        let docs = self.doc_lengths.len() as f32;
+        let df = document_frequency as f32;
+        let idf = ((docs - df + 0.5) / (df + 0.5) + 1.0).ln();
+
+        let tf = posting.body_hits as f32 + TITLE_WEIGHT * posting.title_hits as f32;
+        let length = self.doc_lengths[posting.doc] as f32;
+        let norm = if self.average_length > 0.0 {
+            1.0 - B + B * length / self.average_length
+        } else {
+            1.0
+        };
+
+        idf * tf * (K1 + 1.0) / (tf + K1 * norm)
+    }
+}
+
+/// Split text into lowercase alphanumeric terms.
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// The number of single-character insertions, deletions and substitutions
+/// that turn `a` into `b` (Levenshtein distance).
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_ch) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_ch) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_ch != *b_ch);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// A short excerpt of `markdown` around the first query match, split into
+/// `(text, is_match)` segments so the caller can highlight matching words.
+pub fn snippet(markdown: &str, query: &str, max_chars: usize) -> Vec<(String, bool)> {
+    let text = plain_text(markdown);
+    let terms: Vec<String> = tokenize(query).collect();
+    let is_match = |word: &str| {
+        let word = word.to_lowercase();
+        terms.iter().any(|term| word.starts_with(term.as_str()))
+    };
+
+    // Byte ranges of every word in `text`.
+    let mut words = Vec::new();
+    let mut start = None;
+    for (index, ch) in text.char_indices() {
+        match (ch.is_alphanumeric(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(word_start)) => {
+                words.push((word_start, index));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(word_start) = start {
+        words.push((word_start, text.len()));
+    }
+
+    // Start a little before the first match so it has some context.
+    let first = words
+        .iter()
+        .position(|&(from, to)| is_match(&text[from..to]))
+        .unwrap_or(0);
+    let mut window_start = first;
+    while window_start > 0 && words[first].0 - words[window_start - 1].0 < max_chars / 3 {
+        window_start -= 1;
+    }
+    let from = words.get(window_start).map_or(0, |&(from, _)| from);
+    let mut to = words.get(window_start).map_or(text.len(), |&(_, to)| to);
+    for &(_, word_end) in &words[window_start..] {
+        if word_end - from > max_chars {
+            break;
+        }
+        to = word_end;
+    }
+    // Keep trailing punctuation when the rest of the text fits.
+    if words.last().map_or(true, |&(_, last_end)| last_end <= to) {
+        to = text.len();
+    }
+
+    let mut segments = Vec::new();
+    if window_start > 0 {
+        segments.push(("… ".to_string(), false));
+    }
+    let mut cursor = from;
+    for &(word_start, word_end) in &words[window_start..] {
+        if word_end > to {
+            break;
+        }
+        if is_match(&text[word_start..word_end]) {
+            if cursor < word_start {
+                segments.push((text[cursor..word_start].to_string(), false));
+            }
+            segments.push((text[word_start..word_end].to_string(), true));
+            cursor = word_end;
+        }
+    }
+    if cursor < to {
+        segments.push((text[cursor..to].to_string(), false));
+    }
+    if to < text.len() {
+        segments.push((" …".to_string(), false));
+    }
+    segments
+}
+
+/// Markdown with formatting characters removed and whitespace collapsed.
+fn plain_text(markdown: &str) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    for word in markdown.split_whitespace() {
+        let word = word.trim_matches(|ch| matches!(ch, '#' | '*' | '_' | '`' | '>' | '~'));
+        if word.is_empty() || word.chars().all(|ch| ch == '-' || ch == '=') {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(word);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn docs(index: &SearchIndex, query: &str) -> Vec<usize> {
+        index.search(query).into_iter().map(|hit| hit.doc).collect()
+    }
+
+    #[test]
+    fn every_term_must_match() {
+        let index = SearchIndex::new([
+            ("Rust", "rust and wasm"),
+            ("Go", "go and wasm"),
+            ("Notes", "nothing here"),
+        ]);
+        assert_eq!(docs(&index, "wasm rust"), [0]);
+        assert_eq!(docs(&index, "wasm"), [0, 1]);
+        assert!(docs(&index, "python").is_empty());
+        assert!(docs(&index, "  ").is_empty());
+    }
+
+    #[test]
+    fn title_hits_outrank_body_hits() {
+        let index = SearchIndex::new([
+            ("Cooking", "a note on rust stains"),
+            ("Rust", "a note on cooking"),
+        ]);
+        assert_eq!(docs(&index, "rust"), [1, 0]);
+    }
+
+    #[test]
+    fn rarer_terms_and_more_hits_rank_higher() {
+        let index = SearchIndex::new([
+            ("One", "egui egui egui app"),
+            ("Two", "egui app"),
+            ("Three", "app"),
+        ]);
+        assert_eq!(docs(&index, "egui"), [0, 1]);
+        let hits = index.search("egui app");
+        assert!(hits[0].score > hits[1].score);
+    }
+
+    #[test]
+    fn the_last_term_matches_as_a_prefix() {
+        let index = SearchIndex::new([("A", "typestate pattern"), ("B", "type pattern")]);
+        assert_eq!(docs(&index, "type"), [1, 0]);
+        assert!(docs(&index, "typ pattern").is_empty());
+        assert_eq!(docs(&index, "pattern types"), [0]);
+    }
+
+    #[test]
+    fn similar_documents_share_distinctive_terms() {
+        let index = SearchIndex::new([
+            ("Rust and wasm", "the rust wasm egui app"),
+            ("Rust types", "the rust type system"),
+            ("Cooking", "the pasta recipe"),
+        ]);
+        let similar = index.similar(0);
+        assert_eq!(similar.iter().map(|hit| hit.doc).collect::<Vec<_>>(), [1]);
+        assert!(similar[0].score > 0.0 && similar[0].score <= 1.0);
+        // "the" is in every document, so it links nothing.
+        assert!(index.similar(2).is_empty());
+    }
+
+    #[test]
+    fn tokenize_lowercases_alphanumeric_runs() {
+        let terms: Vec<String> = tokenize("Rust's egui-0.27, WASM!").collect();
+        assert_eq!(terms, ["rust", "s", "egui", "0", "27", "wasm"]);
+    }
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        assert_eq!(edit_distance("about", "about"), 0);
+        assert_eq!(edit_distance("abot", "about"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
@@ -0,0 +1,705 @@
+//! Site-wide configuration, read from `site.toml` at the repository root.
+//!
+//! Forks of the site change its identity (name, accent color, navigation,
+//! footer, meta tags and the home page profile) by editing that file rather
+//! than Rust code. The app embeds it, and `build.rs` parses it as well so that
+//! a malformed config fails the build.
+//!
+//! This file is also compiled into `build.rs`, which means it must only
+//! depend on `toml_edit`.
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use toml_edit::{Document, Item, TableLike};
+
+/// Everything in `site.toml`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SiteConfig {
+    /// Native window title, page titles and feed title.
+    pub title: String,
+    /// Default `<meta name="description">` and feed description.
+    pub description: String,
+    /// Feed author; defaults to the profile name.
+    pub author: String,
+    /// Links, highlights and selections, as RGB.
+    pub accent: [u8; 3],
+    /// Shown at the bottom of every page.
+    pub footer: Option<String>,
+    /// Top navigation, in order.
+    pub nav: Vec<NavEntry>,
+    /// Extra `<meta>` tags for the pre-rendered pages, as `(name, content)`.
+    pub meta: Vec<(String, String)>,
+    pub profile: Profile,
+}
+
+/// The accent color used when `site.toml` doesn't set one.
+pub const DEFAULT_ACCENT: [u8; 3] = [22, 163, 74];
+
+/// One `[[nav]]` entry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NavEntry {
+    pub label: String,
+    pub link: NavLink,
+    pub visibility: NavVisibility,
+    /// Lower comes first; entries without one count as 0, and ties keep
+    /// file order.
+    pub order: Option<i32>,
+}
+
+impl NavEntry {
+    fn section(label: &str, section: NavSection, visibility: NavVisibility) -> Self {
+        Self {
+            label: label.to_string(),
+            link: NavLink::Section(section),
+            visibility,
+            order: None,
+        }
+    }
+}
+
+/// Where a nav entry leads: `page = ".."` or `url = ".."`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NavLink {
+    Section(NavSection),
+    /// Another site, opened in a new tab.
+    External(String),
+}
+
+/// When a nav entry is shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NavVisibility {
+    #[default]
+    Always,
+    /// Never shown; the page is still reachable by its URL.
+    Hidden,
+    /// Only while the private blog is unlocked.
+    Unlocked,
+}
+
+impl NavVisibility {
+    pub const ALL: [NavVisibility; 3] = [
+        NavVisibility::Always,
+        NavVisibility::Hidden,
+        NavVisibility::Unlocked,
+    ];
+
+    /// The value written in `site.toml`.
+    pub fn name(self) -> &'static str {
+        match self {
+            NavVisibility::Always => "always",
+            NavVisibility::Hidden => "hidden",
+            NavVisibility::Unlocked => "unlocked",
+        }
+    }
+}
+
+/// The parts of the site a nav entry can point at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavSection {
+    Home,
+    Projects,
+    Blog,
+    PrivateBlog,
+}
+
+impl NavSection {
+    pub const ALL: [NavSection; 4] = [
+        NavSection::Home,
+        NavSection::Projects,
+        NavSection::Blog,
+        NavSection::PrivateBlog,
+    ];
+
+    /// The value written in `site.toml`.
+    pub fn name(self) -> &'static str {
+        match self {
+            NavSection::Home => "home",
+            NavSection::Projects => "projects",
+            NavSection::Blog => "blog",
+            NavSection::PrivateBlog => "private",
+        }
+    }
+}
+
+/// The `[profile]` table: what the home page shows.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    /// One line under the name.
+    pub tagline: Option<String>,
+    /// Markdown shown under the tagline.
+    pub bio: Option<String>,
+    /// Image path relative to the repository root; `build.rs` embeds it.
+    pub avatar: Option<String>,
+    pub links: Vec<ProfileLink>,
+}
+
+/// One `[[profile.links]]` entry.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileLink {
+    pub label: String,
+    pub url: String,
+    pub icon: Option<LinkIcon>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LinkIcon {
+    Github,
+    Linkedin,
+    Twitter,
+    Mastodon,
+    Email,
+    Resume,
+    Website,
+    Rss,
+    Link,
+}
+
+impl LinkIcon {
+    pub const ALL: [LinkIcon; 9] = [
+        LinkIcon::Github,
+        LinkIcon::Linkedin,
+        LinkIcon::Twitter,
+        LinkIcon::Mastodon,
+        LinkIcon::Email,
+        LinkIcon::Resume,
+        LinkIcon::Website,
+        LinkIcon::Rss,
+        LinkIcon::Link,
+    ];
+
+    /// The value written in `site.toml`.
+    pub fn name(self) -> &'static str {
+        match self {
+            LinkIcon::Github => "github",
+            LinkIcon::Linkedin => "linkedin",
+            LinkIcon::Twitter => "twitter",
+            LinkIcon::Mastodon => "mastodon",
+            LinkIcon::Email => "email",
+            LinkIcon::Resume => "resume",
+            LinkIcon::Website => "website",
+            LinkIcon::Rss => "rss",
+            LinkIcon::Link => "link",
+        }
+    }
+
+    /// A glyph from egui's bundled fonts.
+    pub fn glyph(self) -> char {
+        match self {
+            LinkIcon::Github => '\u{e624}',
+            LinkIcon::Twitter => '\u{e603}',
+            LinkIcon::Linkedin => '💼',
+            LinkIcon::Mastodon => '🐘',
+            LinkIcon::Email => '✉',
+            LinkIcon::Resume => '📄',
+            LinkIcon::Website => '🌐',
+            LinkIcon::Rss => '📰',
+            LinkIcon::Link => '🔗',
+        }
+    }
+}
+
+/// A problem with `site.toml`.
+#[derive(Debug)]
+pub struct SiteError {
+    message: String,
+}
+
+impl SiteError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SiteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "site.toml: {}", self.message)
+    }
+}
+
+impl std::error::Error for SiteError {}
+
+/// The `site.toml` embedded at compile time, parsed on first use.
+///
+/// `build.rs` rejects an invalid config, so this only panics at startup if
+/// that check was bypassed.
+pub fn embedded() -> &'static SiteConfig {
+    static CONFIG: OnceLock<SiteConfig> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        parse(include_str!("../site.toml"))
+            .unwrap_or_else(|err| panic!("{} (build.rs should have rejected it)", err))
+    })
+}
+
+/// Parse `site.toml`.
+pub fn parse(source: &str) -> Result<SiteConfig, SiteError> {
+    let document: Document = source
+        .parse()
+        .map_err(|err: toml_edit::TomlError| SiteError::new(err.to_string()))?;
+
+    let root = Fields::new(document.as_table(), "");
+    root.deny_unknown(&["site", "nav", "meta", "profile"])?;
+
+    let profile = parse_profile(
+        &root
+            .table("profile")?
+            .ok_or_else(|| SiteError::new("missing [profile] table"))?,
+    )?;
+
+    let site = root
+        .table("site")?
+        .ok_or_else(|| SiteError::new("missing [site] table"))?;
+    site.deny_unknown(&["title", "description", "author", "accent", "footer"])?;
+    let accent = match site.string("accent")? {
+        Some(accent) => parse_color(&accent).ok_or_else(|| {
+            site.error(
+                "accent",
+                &format!("`{}` is not a color like \"#16a34a\"", accent),
+            )
+        })?,
+        None => DEFAULT_ACCENT,
+    };
+
+    let nav = if root.table.contains_key("nav") {
+        let mut nav = Vec::new();
+        for entry in root.tables("nav")? {
+            nav.push(parse_nav_entry(&entry)?);
+        }
+        nav
+    } else {
+        vec![
+            NavEntry::section("Home", NavSection::Home, NavVisibility::Always),
+            NavEntry::section("Projects", NavSection::Projects, NavVisibility::Always),
+            NavEntry::section("Blog", NavSection::Blog, NavVisibility::Always),
+            NavEntry::section("Private", NavSection::PrivateBlog, NavVisibility::Unlocked),
+        ]
+    };
+
+    let mut meta = Vec::new();
+    if let Some(table) = root.table("meta")? {
+        for (name, _) in table.table.iter() {
+            let content = table.required_string(name)?;
+            meta.push((name.to_string(), content));
+        }
+    }
+
+    Ok(SiteConfig {
+        title: site.required_string("title")?,
+        description: site.required_string("description")?,
+        author: site
+            .string("author")?
+            .unwrap_or_else(|| profile.name.clone()),
+        accent,
+        footer: site.string("footer")?,
+        nav,
+        meta,
+        profile,
+    })
+}
+
+/// `#rrggbb` as RGB.
+fn parse_color(value: &str) -> Option<[u8; 3]> {
+    let hex = value.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+fn unknown<const N: usize>(what: &str, value: &str, expected: [&str; N]) -> String {
+    format!(
+        "unknown {} `{}`, expected one of {}",
+        what,
+        value,
+        expected.join(", ")
+    )
+}
+
+fn parse_nav_entry(entry: &Fields<'_>) -> Result<NavEntry, SiteError> {
+    entry.deny_unknown(&["label", "page", "url", "visibility", "order"])?;
+
+    let link = match (entry.string("page")?, entry.string("url")?) {
+        (Some(page), None) => NavLink::Section(
+            NavSection::ALL
+                .into_iter()
+                .find(|section| section.name() == page)
+                .ok_or_else(|| {
+                    entry.error(
+                        "page",
+                        &unknown("page", &page, NavSection::ALL.map(NavSection::name)),
+                    )
+                })?,
+        ),
+        (None, Some(url)) => NavLink::External(url),
+        (Some(_), Some(_)) => {
+            return Err(entry.error("url", "set either `page` or `url`, not both"))
+        }
+        (None, None) => return Err(entry.error("page", "missing required key (or `url`)")),
+    };
+
+    let visibility = match entry.string("visibility")? {
+        Some(name) => NavVisibility::ALL
+            .into_iter()
+            .find(|visibility| visibility.name() == name)
+            .ok_or_else(|| {
+                entry.error(
+                    "visibility",
+                    &unknown(
+                        "visibility",
+                        &name,
+                        NavVisibility::ALL.map(NavVisibility::name),
+                    ),
+                )
+            })?,
+        None => NavVisibility::Always,
+    };
+
+    Ok(NavEntry {
+        label: entry.required_string("label")?,
+        link,
+        visibility,
+        order: entry.integer("order")?,
+    })
+}
+
+fn parse_profile(profile: &Fields<'_>) -> Result<Profile, SiteError> {
+    profile.deny_unknown(&["name", "tagline", "bio", "avatar", "links"])?;
+
+    let mut links = Vec::new();
+    for link in profile.tables("links")? {
+        link.deny_unknown(&["label", "url", "icon"])?;
+        let icon = match link.string("icon")? {
+            Some(name) => Some(
+                LinkIcon::ALL
+                    .into_iter()
+                    .find(|icon| icon.name() == name)
+                    .ok_or_else(|| {
+                        link.error(
+                            "icon",
+                            &unknown("icon", &name, LinkIcon::ALL.map(LinkIcon::name)),
+                        )
+                    })?,
+            ),
+            None => None,
+        };
+        links.push(ProfileLink {
+            label: link.required_string("label")?,
+            url: link.required_string("url")?,
+            icon,
+        });
+    }
+
+    Ok(Profile {
+        name: profile.required_string("name")?,
+        tagline: profile.string("tagline")?,
+        bio: profile.string("bio")?,
+        avatar: profile.string("avatar")?,
+        links,
+    })
+}
+
+/// A table being read, with its dotted path for error messages.
+struct Fields<'a> {
+    table: &'a dyn TableLike,
+    path: String,
+}
+
+impl<'a> Fields<'a> {
+    fn new(table: &'a dyn TableLike, path: &str) -> Self {
+        Self {
+            table,
+            path: path.to_string(),
+        }
+    }
+
+    fn key_path(&self, key: &str) -> String {
+        if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.path, key)
+        }
+    }
+
+    fn error(&self, key: &str, message: &str) -> SiteError {
+        SiteError::new(format!("{}: {}", self.key_path(key), message))
+    }
+
+    fn deny_unknown(&self, known: &[&str]) -> Result<(), SiteError> {
+        match self.table.iter().find(|(key, _)| !known.contains(key)) {
+            Some((key, _)) => Err(self.error(key, "unknown key")),
+            None => Ok(()),
+        }
+    }
+
+    /// A trimmed string, `None` if the key is missing or blank.
+    fn string(&self, key: &str) -> Result<Option<String>, SiteError> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(item) => match item.as_str() {
+                Some(value) if value.trim().is_empty() => Ok(None),
+                Some(value) => Ok(Some(value.trim().to_string())),
+                None => Err(self.error(key, "expected a string")),
+            },
+        }
+    }
+
+    fn integer(&self, key: &str) -> Result<Option<i32>, SiteError> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(item) => match item.as_integer().map(i32::try_from) {
+                Some(Ok(value)) => Ok(Some(value)),
+                _ => Err(self.error(key, "expected a whole number")),
+            },
+        }
+    }
+
+    fn required_string(&self, key: &str) -> Result<String, SiteError> {
+        self.string(key)?
+            .ok_or_else(|| self.error(key, "missing required key"))
+    }
+
+    fn table(&self, key: &str) -> Result<Option<Fields<'a>>, SiteError> {
+        match self.table.get(key) {
+            None => Ok(None),
+            Some(item) => match item.as_table_like() {
+                Some(table) => Ok(Some(Fields::new(table, &self.key_path(key)))),
+                None => Err(self.error(key, "expected a table")),
+            },
+        }
+    }
+
+    /// An array of tables, written either as `[[key]]` sections or as an
+    /// inline array of `{ .. }` tables.
+    fn tables(&self, key: &str) -> Result<Vec<Fields<'a>>, SiteError> {
+        let path = self.key_path(key);
+        let at = |index: usize| format!("{}[{}]", path, index);
+        match self.table.get(key) {
+            None => Ok(Vec::new()),
+            Some(Item::ArrayOfTables(tables)) => Ok(tables
+                .iter()
+                .enumerate()
+                .map(|(index, table)| Fields::new(table, &at(index)))
+                .collect()),
+            Some(Item::Value(toml_edit::Value::Array(array))) => array
+                .iter()
+                .enumerate()
+                .map(|(index, value)| match value.as_inline_table() {
+                    Some(table) => Ok(Fields::new(table, &at(index))),
+                    None => Err(SiteError::new(format!("{}: expected a table", at(index)))),
+                })
+                .collect(),
+            Some(_) => Err(self.error(key, "expected an array of tables")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SITE: &str = "[site]\ntitle = \"T\"\ndescription = \"D\"\n";
+    const PROFILE: &str = "[profile]\nname = \"N\"\n";
+
+    /// A config with the required tables plus `extra` (keys for `[site]`
+    /// when it starts without a table header, otherwise more tables).
+    fn config(extra: &str) -> String {
+        if extra.trim_start().starts_with('[') {
+            format!("{}{}{}", SITE, PROFILE, extra)
+        } else {
+            format!("{}{}{}", SITE, extra, PROFILE)
+        }
+    }
+
+    fn error(source: &str) -> String {
+        parse(source).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn the_shipped_config_parses() {
+        let config = parse(include_str!("../site.toml")).unwrap();
+        assert_eq!(&config, embedded());
+        assert!(!config.title.is_empty());
+        assert!(!config.nav.is_empty());
+    }
+
+    #[test]
+    fn optional_keys_have_defaults() {
+        let config = parse(&config("footer = \"  \"\n")).unwrap();
+        assert_eq!(config.title, "T");
+        assert_eq!(config.author, "N");
+        assert_eq!(config.accent, DEFAULT_ACCENT);
+        assert_eq!(config.footer, None);
+        assert!(config.meta.is_empty());
+        assert!(config.profile.links.is_empty());
+        let links: Vec<NavLink> = config.nav.iter().map(|entry| entry.link.clone()).collect();
+        assert_eq!(links, NavSection::ALL.map(NavLink::Section));
+        assert_eq!(config.nav[3].visibility, NavVisibility::Unlocked);
+    }
+
+    #[test]
+    fn reads_every_key() {
+        let config = parse(
+            "nav = [{ label = \"Blog\", page = \"blog\", order = -1 },\
+                    { label = \"CV\", url = \"https://example.com\", visibility = \"hidden\" }]\n\
+             [site]\ntitle = \"T\"\ndescription = \"D\"\n\
+             author = \"A\"\naccent = \"#FF8000\"\nfooter = \" F \"\n\
+             [meta]\n\"og:locale\" = \"en\"\n\
+             [profile]\nname = \"N\"\n\
+             [[profile.links]]\nlabel = \"Mail\"\nurl = \"mailto:a@b.c\"\nicon = \"email\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.author, "A");
+        assert_eq!(config.accent, [255, 128, 0]);
+        assert_eq!(config.footer.as_deref(), Some("F"));
+        assert_eq!(
+            config.nav,
+            [
+                NavEntry {
+                    label: "Blog".to_string(),
+                    link: NavLink::Section(NavSection::Blog),
+                    visibility: NavVisibility::Always,
+                    order: Some(-1),
+                },
+                NavEntry {
+                    label: "CV".to_string(),
+                    link: NavLink::External("https://example.com".to_string()),
+                    visibility: NavVisibility::Hidden,
+                    order: None,
+                },
+            ]
+        );
+        assert_eq!(config.meta, [("og:locale".to_string(), "en".to_string())]);
+        assert_eq!(
+            config.profile.links,
+            [ProfileLink {
+                label: "Mail".to_string(),
+                url: "mailto:a@b.c".to_string(),
+                icon: Some(LinkIcon::Email),
+            }]
+        );
+    }
+
+    #[test]
+    fn invalid_toml_is_reported() {
+        assert!(error("[site\n").starts_with("site.toml: "));
+    }
+
+    #[test]
+    fn tables_are_required() {
+        assert_eq!(error(PROFILE), "site.toml: missing [site] table");
+        assert_eq!(error(SITE), "site.toml: missing [profile] table");
+        assert_eq!(
+            error(&format!("site = 1\n{}", PROFILE)),
+            "site.toml: site: expected a table"
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert_eq!(error(&config("[extra]\n")), "site.toml: extra: unknown key");
+        assert_eq!(
+            error(&config("colour = \"#000000\"\n")),
+            "site.toml: site.colour: unknown key"
+        );
+        assert_eq!(
+            error(&config(
+                "[[nav]]\nlabel = \"a\"\npage = \"home\"\nicon = \"x\"\n"
+            )),
+            "site.toml: nav[0].icon: unknown key"
+        );
+        assert_eq!(
+            error(&config(
+                "[[profile.links]]\nlabel = \"a\"\nurl = \"b\"\nhref = \"c\"\n"
+            )),
+            "site.toml: profile.links[0].href: unknown key"
+        );
+    }
+
+    #[test]
+    fn missing_and_mistyped_values_are_rejected() {
+        assert_eq!(
+            error(&format!("[site]\ndescription = \"D\"\n{}", PROFILE)),
+            "site.toml: site.title: missing required key"
+        );
+        assert_eq!(
+            error(&format!(
+                "[site]\ntitle = \" \"\ndescription = \"D\"\n{}",
+                PROFILE
+            )),
+            "site.toml: site.title: missing required key"
+        );
+        assert_eq!(
+            error(&config("footer = 1\n")),
+            "site.toml: site.footer: expected a string"
+        );
+        assert_eq!(
+            error(&config("[meta]\nrobots = false\n")),
+            "site.toml: meta.robots: expected a string"
+        );
+        assert_eq!(
+            error(&config(
+                "[[nav]]\nlabel = \"a\"\npage = \"home\"\norder = \"1\"\n"
+            )),
+            "site.toml: nav[0].order: expected a whole number"
+        );
+        assert_eq!(
+            error(&config("[[nav]]\npage = \"home\"\n")),
+            "site.toml: nav[0].label: missing required key"
+        );
+    }
+
+    #[test]
+    fn accent_must_be_a_hex_color() {
+        for accent in ["green", "#fff", "#12345g", "16a34a"] {
+            assert_eq!(
+                error(&config(&format!("accent = \"{}\"\n", accent))),
+                format!(
+                    "site.toml: site.accent: `{}` is not a color like \"#16a34a\"",
+                    accent
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn nav_entries_are_checked() {
+        assert_eq!(
+            error(&format!("nav = 1\n{}{}", SITE, PROFILE)),
+            "site.toml: nav: expected an array of tables"
+        );
+        assert_eq!(
+            error(&format!("nav = [1]\n{}{}", SITE, PROFILE)),
+            "site.toml: nav[0]: expected a table"
+        );
+        assert_eq!(
+            error(&config("[[nav]]\nlabel = \"a\"\npage = \"about\"\n")),
+            "site.toml: nav[0].page: unknown page `about`, expected one of home, projects, blog, private"
+        );
+        assert_eq!(
+            error(&config(
+                "[[nav]]\nlabel = \"a\"\npage = \"home\"\nurl = \"b\"\n"
+            )),
+            "site.toml: nav[0].url: set either `page` or `url`, not both"
+        );
+        assert_eq!(
+            error(&config("[[nav]]\nlabel = \"a\"\n")),
+            "site.toml: nav[0].page: missing required key (or `url`)"
+        );
+        assert_eq!(
+            error(&config("[[nav]]\nlabel = \"a\"\npage = \"home\"\nvisibility = \"never\"\n")),
+            "site.toml: nav[0].visibility: unknown visibility `never`, expected one of always, hidden, unlocked"
+        );
+    }
+
+    #[test]
+    fn profile_link_icons_are_checked() {
+        assert_eq!(
+            error(&config("[[profile.links]]\nlabel = \"a\"\nurl = \"b\"\nicon = \"x\"\n")),
+            "site.toml: profile.links[0].icon: unknown icon `x`, expected one of github, linkedin, twitter, mastodon, email, resume, website, rss, link"
+        );
+    }
+}
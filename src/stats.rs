@@ -0,0 +1,192 @@
+//! How long posts are, how long they take to read, and how much was written
+//! when.
+
+use chrono::Datelike;
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+use crate::headings::PARSE_OPTIONS;
+use crate::BlogPost;
+
+/// A typical adult's silent reading speed.
+const WORDS_PER_MINUTE: usize = 230;
+
+/// Counted once per post when it is loaded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PostStats {
+    /// Words of prose, including inline code but not code blocks, which are
+    /// rarely read word by word.
+    pub words: usize,
+    pub headings: usize,
+    pub links: usize,
+}
+
+impl PostStats {
+    pub fn of(markdown: &str) -> Self {
+        let mut stats = Self::default();
+        let mut in_code_block = false;
+        for event in Parser::new_ext(markdown, PARSE_OPTIONS) {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::End(TagEnd::CodeBlock) => in_code_block = false,
+                Event::Start(Tag::Heading { .. }) => stats.headings += 1,
+                Event::Start(Tag::Link { .. }) => stats.links += 1,
+                Event::Text(text) | Event::Code(text) if !in_code_block => {
+                    // Punctuation split off by markup, like the `.` after
+                    // inline code, is not a word.
+                    stats.words += text
+                        .split_whitespace()
+                        .filter(|word| word.chars().any(char::is_alphanumeric))
+                        .count();
+                }
+                _ => {}
+            }
+        }
+        stats
+    }
+
+    /// Whole minutes, rounded up, and at least one.
+    pub fn reading_minutes(&self) -> usize {
+        self.words.div_ceil(WORDS_PER_MINUTE).max(1)
+    }
+
+    /// "4 min read".
+    pub fn reading_time(&self) -> String {
+        format!("{} min read", self.reading_minutes())
+    }
+}
+
+/// What was published in some period.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub posts: usize,
+    pub words: usize,
+    /// The sum of each post's rounded [`PostStats::reading_minutes`].
+    pub reading_minutes: usize,
+    pub headings: usize,
+    pub links: usize,
+}
+
+impl Totals {
+    fn add(&mut self, stats: &PostStats) {
+        self.posts += 1;
+        self.words += stats.words;
+        self.reading_minutes += stats.reading_minutes();
+        self.headings += stats.headings;
+        self.links += stats.links;
+    }
+}
+
+/// Every post counted together.
+pub fn overall(posts: &[BlogPost]) -> Totals {
+    let mut totals = Totals::default();
+    for post in posts {
+        totals.add(&post.stats);
+    }
+    totals
+}
+
+/// Totals per calendar year of the posts' `date`, newest first.
+pub fn by_year(posts: &[BlogPost]) -> Vec<(i32, Totals)> {
+    by_period(posts, |post| post.meta.date.year())
+}
+
+/// Totals per `(year, month)`, newest first. Months without posts are left
+/// out.
+pub fn by_month(posts: &[BlogPost]) -> Vec<((i32, u32), Totals)> {
+    by_period(posts, |post| {
+        (post.meta.date.year(), post.meta.date.month())
+    })
+}
+
+fn by_period<K: Ord>(posts: &[BlogPost], period: impl Fn(&BlogPost) -> K) -> Vec<(K, Totals)> {
+    let mut periods = std::collections::BTreeMap::new();
+    for post in posts {
+        periods
+            .entry(period(post))
+            .or_insert_with(Totals::default)
+            .add(&post.stats);
+    }
+    periods.into_iter().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_prose_inline_code_headings_and_links() {
+        let stats = PostStats::of(
+            "# Two words\n\nSee [the docs](https://example.com) and `cargo run`.\n\n\
+             ```rust\nfn main() { let not = counted; }\n```\n\n    indented code too\n",
+        );
+        assert_eq!(
+            stats,
+            PostStats {
+                words: 8,
+                headings: 1,
+                links: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn reading_time_rounds_up_to_at_least_a_minute() {
+        let minutes = |words| {
+            PostStats {
+                words,
+                ..PostStats::default()
+            }
+            .reading_minutes()
+        };
+        assert_eq!(minutes(0), 1);
+        assert_eq!(minutes(1), 1);
+        assert_eq!(minutes(WORDS_PER_MINUTE), 1);
+        assert_eq!(minutes(WORDS_PER_MINUTE + 1), 2);
+        assert_eq!(PostStats::default().reading_time(), "1 min read");
+    }
+
+    #[test]
+    fn totals_by_period_are_newest_first() {
+        let words = "word ".repeat(WORDS_PER_MINUTE + 1);
+        let posts = BlogPost::load_all(
+            [
+                (
+                    "a.md",
+                    format!("---\ntitle: a\ndate: 2023-05-01\n---\n{}", words),
+                ),
+                (
+                    "b.md",
+                    "---\ntitle: b\ndate: 2024-01-10\n---\n[x](y)\n".to_string(),
+                ),
+                (
+                    "c.md",
+                    "---\ntitle: c\ndate: 2024-01-20\n---\n# H\n".to_string(),
+                ),
+                (
+                    "d.md",
+                    "---\ntitle: d\ndate: 2024-03-01\n---\none two\n".to_string(),
+                ),
+            ]
+            .iter()
+            .map(|(name, content)| (*name, content.as_str())),
+        );
+
+        let total = overall(&posts);
+        assert_eq!(total.posts, 4);
+        assert_eq!(total.words, WORDS_PER_MINUTE + 1 + 1 + 1 + 2);
+        assert_eq!(total.reading_minutes, 2 + 1 + 1 + 1);
+        assert_eq!((total.headings, total.links), (1, 1));
+
+        let years: Vec<(i32, usize)> = by_year(&posts)
+            .into_iter()
+            .map(|(year, totals)| (year, totals.posts))
+            .collect();
+        assert_eq!(years, [(2024, 3), (2023, 1)]);
+
+        let months: Vec<((i32, u32), usize)> = by_month(&posts)
+            .into_iter()
+            .map(|(month, totals)| (month, totals.posts))
+            .collect();
+        assert_eq!(months, [((2024, 3), 1), ((2024, 1), 2), ((2023, 5), 1)]);
+    }
+}
@@ -0,0 +1,170 @@
+//! Encryption of private posts.
+//!
+//! Private posts are sealed at build time so that only ciphertext ends up in
+//! the binary. Posts are grouped by their `access` frontmatter; each group has
+//! a random content key and every post in it is encrypted with AES-256-GCM
+//! under that key and its own random nonce. The content key is in turn
+//! wrapped (AES-256-GCM again) under every password allowed to read the group,
+//! each derived with PBKDF2-HMAC-SHA256.
+//!
+//! The client derives a key from what the user types, tries to unwrap each
+//! group's content key with it and decrypts the groups that open; a wrong
+//! password simply fails authentication everywhere.
+//!
+//! This file is also compiled into `build.rs` (which does the sealing), so it
+//! must only depend on the crypto crates listed under both dependency tables.
+
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use sha2::Sha256;
+
+pub const SALT_LEN: usize = 16;
+pub const NONCE_LEN: usize = 12;
+pub const KEY_LEN: usize = 32;
+
+/// PBKDF2 rounds. High enough to make offline guessing expensive, low enough
+/// that unlocking in the browser stays well under a second.
+pub const ITERATIONS: u32 = 210_000;
+
+/// Posts without an `access` key belong to this group, which only the main
+/// private blog password opens.
+pub const DEFAULT_GROUP: &str = "default";
+
+/// One AES-GCM ciphertext: either a wrapped content key, or a post whose
+/// plaintext is its file name, a newline, and the full markdown file
+/// (frontmatter included, so titles stay hidden too).
+pub struct Sealed {
+    pub nonce: [u8; NONCE_LEN],
+    pub ciphertext: &'static [u8],
+}
+
+/// The posts of one access group.
+pub struct SealedGroup {
+    pub name: &'static str,
+    /// The group's content key, wrapped once per password that may read it.
+    pub keys: &'static [Sealed],
+    pub posts: &'static [Sealed],
+}
+
+pub type SecretKey = [u8; KEY_LEN];
+
+pub fn derive_key(password: &str, salt: &[u8; SALT_LEN], iterations: u32) -> SecretKey {
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, iterations, &mut key);
+    key
+}
+
+pub fn encrypt(key: &SecretKey, nonce: &[u8; NONCE_LEN], plaintext: &[u8]) -> Vec<u8> {
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+        .encrypt(Nonce::from_slice(nonce), plaintext)
+        .expect("AES-GCM encryption of an in-memory buffer cannot fail")
+}
+
+/// `None` if the key is wrong or the ciphertext was tampered with.
+fn decrypt(key: &SecretKey, sealed: &Sealed) -> Option<Vec<u8>> {
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+        .decrypt(Nonce::from_slice(&sealed.nonce), sealed.ciphertext)
+        .ok()
+}
+
+/// Recover a group's content key using a password-derived key.
+pub fn unwrap_key(key: &SecretKey, group: &SealedGroup) -> Option<SecretKey> {
+    group
+        .keys
+        .iter()
+        .find_map(|wrapped| decrypt(key, wrapped)?.try_into().ok())
+}
+
+/// Decrypt a sealed post into `(file name, markdown)` with its group's
+/// content key.
+pub fn open(key: &SecretKey, sealed: &Sealed) -> Option<(String, String)> {
+    let plaintext = String::from_utf8(decrypt(key, sealed)?).ok()?;
+    let (name, content) = plaintext.split_once('\n')?;
+    Some((name.to_string(), content.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SALT: [u8; SALT_LEN] = [7; SALT_LEN];
+    /// Far below [`ITERATIONS`], to keep the tests fast.
+    const TEST_ITERATIONS: u32 = 1_000;
+
+    fn seal(key: &SecretKey, nonce: u8, plaintext: &[u8]) -> Sealed {
+        let nonce = [nonce; NONCE_LEN];
+        Sealed {
+            nonce,
+            ciphertext: encrypt(key, &nonce, plaintext).leak(),
+        }
+    }
+
+    /// A group holding one post, readable with any of `passwords`.
+    fn group(passwords: &[&str]) -> SealedGroup {
+        let content_key: SecretKey = [42; KEY_LEN];
+        let keys: Vec<Sealed> = passwords
+            .iter()
+            .enumerate()
+            .map(|(index, password)| {
+                let key = derive_key(password, &SALT, TEST_ITERATIONS);
+                seal(&key, index as u8, &content_key)
+            })
+            .collect();
+        SealedGroup {
+            name: DEFAULT_GROUP,
+            keys: keys.leak(),
+            posts: vec![seal(
+                &content_key,
+                99,
+                b"post.md\n---\ntitle: a\n---\nBody\n",
+            )]
+            .leak(),
+        }
+    }
+
+    #[test]
+    fn every_password_opens_the_group() {
+        let group = group(&["main", "friends"]);
+        for password in ["main", "friends"] {
+            let key = derive_key(password, &SALT, TEST_ITERATIONS);
+            let content_key = unwrap_key(&key, &group).expect("password opens the group");
+            assert_eq!(
+                open(&content_key, &group.posts[0]),
+                Some((
+                    "post.md".to_string(),
+                    "---\ntitle: a\n---\nBody\n".to_string()
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn a_wrong_password_opens_nothing() {
+        let group = group(&["main"]);
+        let key = derive_key("mian", &SALT, TEST_ITERATIONS);
+        assert_eq!(unwrap_key(&key, &group), None);
+        assert_eq!(open(&key, &group.posts[0]), None);
+    }
+
+    #[test]
+    fn tampered_ciphertext_is_rejected() {
+        let key = derive_key("main", &SALT, TEST_ITERATIONS);
+        let sealed = seal(&key, 1, b"post.md\nBody");
+        let mut ciphertext = sealed.ciphertext.to_vec();
+        ciphertext[0] ^= 1;
+        let tampered = Sealed {
+            nonce: sealed.nonce,
+            ciphertext: ciphertext.leak(),
+        };
+        assert!(open(&key, &sealed).is_some());
+        assert_eq!(open(&key, &tampered), None);
+    }
+
+    #[test]
+    fn keys_depend_on_password_and_salt() {
+        let key = derive_key("main", &SALT, TEST_ITERATIONS);
+        assert_eq!(key, derive_key("main", &SALT, TEST_ITERATIONS));
+        assert_ne!(key, derive_key("main2", &SALT, TEST_ITERATIONS));
+        assert_ne!(key, derive_key("main", &[8; SALT_LEN], TEST_ITERATIONS));
+    }
+}